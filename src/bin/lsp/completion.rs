@@ -0,0 +1,182 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Completion of string values, decided by the key they belong to.
+//!
+//! The text being edited is usually not valid JSON, so the key is found by scanning the text
+//! before the cursor rather than by parsing it.
+
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, Position, Range, TextEdit};
+use minecraft_json::minecraft::data::advancement::Criterion;
+use minecraft_json::minecraft::data::pack::ResourceKind;
+use minecraft_json::minecraft::data::predicate::Predicate;
+use minecraft_json::minecraft::data::registry::{Registries, RegistryKind};
+use minecraft_json::minecraft::text::ColourName;
+use schemars::schema_for;
+use serde_json::Value;
+use crate::workspace::{self, Pack};
+
+/// Keys of entity fields in trigger conditions, which may be a list of predicates.
+const ENTITY_KEYS: &[&str] = &[
+    "player", "entity", "child", "parent", "partner", "bystander", "lightning", "projectile",
+    "source", "villager", "zombie",
+];
+
+/// Completion items for the string under the cursor in `file` of `pack`.
+pub fn complete(pack: &Pack, file: &str, position: Position) -> Vec<CompletionItem> {
+    let text = match pack.files.get(file) {
+        Some(text) => text,
+        None => return Vec::new(),
+    };
+    let cursor = workspace::offset(text, position);
+    let context = match Context::at(text, cursor) {
+        Some(context) => context,
+        None => return Vec::new(),
+    };
+    let (candidates, kind) = candidates(pack, &context);
+    let range = Range::new(workspace::position(text, context.start), position);
+    candidates.into_iter()
+        .filter(|candidate| candidate.starts_with(&context.prefix)
+            || candidate.trim_start_matches("minecraft:").starts_with(&context.prefix))
+        .map(|candidate| CompletionItem {
+            label: candidate.clone(),
+            kind: Some(kind),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, candidate))),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+fn candidates(pack: &Pack, context: &Context) -> (Vec<String>, CompletionItemKind) {
+    let strings = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+    // only some registries of some versions are embedded, others complete nothing
+    let registry = |kind| {
+        let registries = pack.meta().and_then(|meta| Registries::vanilla(meta.pack_format()));
+        registries.map_or_else(Vec::new, |registries| registries.entries(kind).map(ToString::to_string).collect())
+    };
+    let key = context.key.as_str();
+    match key {
+        "trigger" if !context.in_array => (strings(Criterion::VANILLA_TRIGGERS), CompletionItemKind::EVENT),
+        "condition" if !context.in_array => (strings(Predicate::VANILLA_CONDITIONS), CompletionItemKind::ENUM_MEMBER),
+        "color" if !context.in_array => (colour_names(), CompletionItemKind::COLOR),
+        "parent" if !context.in_array => (pack.ids(ResourceKind::Advancement), CompletionItemKind::REFERENCE),
+        "name" if !context.in_array => (pack.ids(ResourceKind::Predicate), CompletionItemKind::REFERENCE),
+        _ if context.in_array && ENTITY_KEYS.contains(&key) =>
+            (pack.ids(ResourceKind::Predicate), CompletionItemKind::REFERENCE),
+        "recipes" if context.in_array => (pack.ids(ResourceKind::Recipe), CompletionItemKind::REFERENCE),
+        "loot" | "loot_table" => (pack.ids(ResourceKind::LootTable), CompletionItemKind::REFERENCE),
+        "function" if !context.in_array => (pack.ids(ResourceKind::Function), CompletionItemKind::FUNCTION),
+        "dimension" => (registry(RegistryKind::Dimension), CompletionItemKind::VALUE),
+        "potion" => (registry(RegistryKind::Potion), CompletionItemKind::VALUE),
+        "enchantment" => (registry(RegistryKind::Enchantment), CompletionItemKind::VALUE),
+        "structure" | "feature" => (registry(RegistryKind::Structure), CompletionItemKind::VALUE),
+        _ => (Vec::new(), CompletionItemKind::TEXT),
+    }
+}
+
+/// Values of [`ColourName`], as listed in its schema.
+fn colour_names() -> Vec<String> {
+    fn collect(schema: &Value, names: &mut Vec<String>) {
+        match schema {
+            Value::Object(obj) => {
+                let values = obj.get("enum").and_then(Value::as_array).into_iter().flatten();
+                names.extend(values.filter_map(Value::as_str).map(str::to_string));
+                obj.values().for_each(|child| collect(child, names));
+            }
+            Value::Array(items) => items.iter().for_each(|child| collect(child, names)),
+            _ => {}
+        }
+    }
+    let mut names = Vec::new();
+    collect(&serde_json::to_value(schema_for!(ColourName)).unwrap_or_default(), &mut names);
+    names.dedup();
+    names
+}
+
+/// Where the cursor is: inside a string, which is the value of `key`, or an element of an array
+/// which is the value of `key`.
+#[derive(Debug)]
+struct Context {
+    /// Offset just after the opening quote.
+    start: usize,
+    /// Text between the opening quote and the cursor.
+    prefix: String,
+    key: String,
+    in_array: bool,
+}
+
+impl Context {
+    fn at(text: &str, cursor: usize) -> Option<Context> {
+        let line_start = text[..cursor].rfind('\n').map_or(0, |k| k + 1);
+        let quote = opening_quote(&text[line_start..cursor])? + line_start;
+        let start = quote + 1;
+        let prefix = text[start..cursor].to_string();
+        let before = text[..quote].trim_end();
+        if let Some(before) = before.strip_suffix(':') {
+            return Some(Context { start, prefix, key: last_string(before)?, in_array: false });
+        }
+        let bracket = enclosing_bracket(before)?;
+        let before = text[..bracket].trim_end().strip_suffix(':')?;
+        Some(Context { start, prefix, key: last_string(before)?, in_array: true })
+    }
+}
+
+/// Offset of the quote opening the string the end of `line` is in, if any.
+fn opening_quote(line: &str) -> Option<usize> {
+    let mut open = None;
+    let mut escaped = false;
+    for (k, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if open.is_some() => escaped = true,
+            '"' => open = if open.is_some() { None } else { Some(k) },
+            _ => {}
+        }
+    }
+    open
+}
+
+/// The string at the end of `text`, without quotes.
+fn last_string(text: &str) -> Option<String> {
+    let text = text.trim_end().strip_suffix('"')?;
+    let start = text.rfind('"')?;
+    Some(text[start + 1..].to_string())
+}
+
+/// Offset of the `[` of the array whose elements `text` ends in, if it is in an array.
+fn enclosing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let bytes = text.as_bytes();
+    for k in (0..bytes.len()).rev() {
+        let c = bytes[k];
+        if c == b'"' && (k == 0 || bytes[k - 1] != b'\\') {
+            in_string = !in_string;
+        } else if in_string {
+            continue;
+        } else if c == b']' || c == b'}' {
+            depth += 1;
+        } else if c == b'{' {
+            depth = depth.checked_sub(1)?;
+        } else if c == b'[' {
+            if depth == 0 { return Some(k); }
+            depth -= 1;
+        }
+    }
+    None
+}
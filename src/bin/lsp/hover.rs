@@ -0,0 +1,60 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Hover docs, taken from the descriptions in the generated schemas.
+
+use std::collections::HashMap;
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+use serde_json::Value;
+use minecraft_json::minecraft::data::pack::ResourceKind;
+use minecraft_json::minecraft::data::schema;
+use minecraft_json::minecraft::lenient;
+use crate::workspace::{self, Pack};
+
+/// Generated schemas, serialized, by resource kind (`None` for `pack.mcmeta`). Generating a
+/// schema is costly, so each is generated only on first use.
+#[derive(Debug, Default)]
+pub struct Schemas {
+    cache: HashMap<Option<ResourceKind>, Option<Value>>,
+}
+
+impl Schemas {
+    fn get(&mut self, kind: Option<ResourceKind>) -> Option<&Value> {
+        self.cache.entry(kind).or_insert_with(|| {
+            let schema = match kind {
+                None => schema::mcmeta(),
+                Some(kind) => schema::resource(kind)?,
+            };
+            serde_json::to_value(schema).ok()
+        }).as_ref()
+    }
+}
+
+/// Documentation of the key or value under the cursor in `file` of `pack`.
+pub fn hover(schemas: &mut Schemas, pack: &Pack, file: &str, position: Position) -> Option<Hover> {
+    let text = pack.files.get(file)?;
+    let kind = if file == "pack.mcmeta" { None } else { Some(ResourceKind::from_path(file)?.0) };
+    let schema = schemas.get(kind)?;
+    let parsed = lenient::parse(text).ok()?;
+    let (path, span) = parsed.spans.at(workspace::offset(text, position))?;
+    let description = schema::describe_value(schema, &parsed.value, path)?;
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value: description }),
+        range: Some(workspace::range(text, span)),
+    })
+}
@@ -0,0 +1,212 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A language server for data pack JSON files, over stdio. Requires the `lsp` feature.
+//!
+//! Data packs are found in the workspace folder: either the folder itself, or its immediate
+//! subfolders, containing a `pack.mcmeta`. The server provides:
+//!
+//! - diagnostics: syntax and type errors, unknown triggers, version issues, duplicate keys, and
+//!   references to advancements, predicates etc. not defined in the pack;
+//! - completion: trigger names, condition names, colour names, IDs of resources in the pack, and
+//!   IDs of dimensions, potions, enchantments and structures for pack formats with embedded
+//!   vanilla registries (see `Registries::vanilla`);
+//! - hover docs, taken from the doc comments of the typed models.
+//!
+//! Everything is computed locally; the server never accesses the network.
+
+mod completion;
+mod hover;
+mod workspace;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::PathBuf;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, Diagnostic, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, HoverParams, HoverProviderCapability,
+    InitializeParams, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use serde::de::DeserializeOwned;
+use crate::hover::Schemas;
+use crate::workspace::Workspace;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["\"".to_string(), ":".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    };
+    let params: InitializeParams = serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?)?;
+    let workspace = Workspace::load(&root(&params).unwrap_or_default());
+    let mut server = Server { connection, workspace, schemas: Schemas::default() };
+    for k in 0..server.workspace.packs.len() {
+        let diagnostics = server.workspace.packs[k].check_all();
+        server.publish(k, diagnostics)?;
+    }
+    server.run()?;
+    // the writer thread stops only when the connection is dropped
+    drop(server);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// The workspace folder, or the deprecated root URI for older clients.
+fn root(params: &InitializeParams) -> Option<PathBuf> {
+    let folder = params.workspace_folders.as_ref().and_then(|folders| folders.first());
+    #[allow(deprecated)]
+    let uri = folder.map(|folder| &folder.uri).or(params.root_uri.as_ref())?;
+    uri.to_file_path().ok()
+}
+
+struct Server {
+    connection: Connection,
+    workspace: Workspace,
+    schemas: Schemas,
+}
+
+impl Server {
+    fn run(&mut self) -> Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let id = request.id.clone();
+                    let response = self.handle_request(request).unwrap_or_else(|err| {
+                        Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string())
+                    });
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Respond to a request, or fail if its parameters are malformed.
+    fn handle_request(&mut self, request: Request) -> serde_json::Result<Response> {
+        match request.method.as_str() {
+            Completion::METHOD => {
+                let params: CompletionParams = serde_json::from_value(request.params)?;
+                let document = params.text_document_position;
+                let items = self.file(&document.text_document.uri)
+                    .map(|(k, file)| completion::complete(&self.workspace.packs[k], &file, document.position))
+                    .unwrap_or_default();
+                Ok(Response::new_ok(request.id, CompletionResponse::Array(items)))
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = serde_json::from_value(request.params)?;
+                let document = params.text_document_position_params;
+                let hover = self.file(&document.text_document.uri).and_then(|(k, file)| {
+                    hover::hover(&mut self.schemas, &self.workspace.packs[k], &file, document.position)
+                });
+                Ok(Response::new_ok(request.id, hover))
+            }
+            method => Ok(Response::new_err(request.id, ErrorCode::MethodNotFound as i32,
+                                           format!("unsupported method '{}'", method))),
+        }
+    }
+
+    /// Handle a notification. Notifications cannot be answered, so those with malformed
+    /// parameters are logged and ignored.
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        let changed = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = match params(&notification.method, notification.params) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+                let document = params.text_document;
+                let path = document.uri.to_file_path().ok();
+                path.and_then(|path| self.workspace.update(&path, document.text))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = match params(&notification.method, notification.params) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+                let path = params.text_document.uri.to_file_path().ok();
+                // full synchronisation: the last change holds the whole text
+                let text = params.content_changes.into_iter().last().map(|change| change.text);
+                path.zip(text).and_then(|(path, text)| self.workspace.update(&path, text))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = match params(&notification.method, notification.params) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+                params.text_document.uri.to_file_path().ok().and_then(|path| self.workspace.reset(&path))
+            }
+            _ => None,
+        };
+        match changed {
+            Some((k, file)) => {
+                let diagnostics = self.workspace.packs[k].check_file(&file);
+                self.publish(k, diagnostics)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The pack containing the document at `uri`, and the path of the document in the pack.
+    fn file(&self, uri: &Url) -> Option<(usize, String)> {
+        self.workspace.locate(&uri.to_file_path().ok()?)
+    }
+
+    /// Publish diagnostics for files in a pack. Files without any diagnostics are included, so
+    /// that stale diagnostics are cleared, e.g. for deleted files.
+    fn publish(&self, k: usize, diagnostics: BTreeMap<String, Vec<Diagnostic>>) -> Result<()> {
+        let pack = &self.workspace.packs[k];
+        for (file, diagnostics) in diagnostics {
+            if let Ok(uri) = Url::from_file_path(pack.root.join(&file)) {
+                self.send_diagnostics(uri, diagnostics)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+}
+
+/// Parameters of a notification, or `None` if they are malformed, which is logged to stderr.
+fn params<P: DeserializeOwned>(method: &str, params: serde_json::Value) -> Option<P> {
+    serde_json::from_value(params)
+        .map_err(|err| eprintln!("ignoring '{}' notification with malformed parameters: {}", method, err))
+        .ok()
+}
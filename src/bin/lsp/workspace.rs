@@ -0,0 +1,350 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Data packs in the workspace folder, and their diagnostics.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use minecraft_json::minecraft::data::advancement::Criterion;
+use minecraft_json::minecraft::data::meta::McMeta;
+use minecraft_json::minecraft::data::pack::{DataPack, LoadErrorKind, ResourceKind};
+use minecraft_json::minecraft::data::validate::Validator;
+use minecraft_json::minecraft::lenient;
+use minecraft_json::minecraft::span::{Span, Spans};
+
+/// All data packs in the workspace folder: either the folder itself, or its immediate
+/// subfolders, containing a `pack.mcmeta`.
+#[derive(Debug, Default)]
+pub struct Workspace {
+    pub packs: Vec<Pack>,
+}
+
+/// A data pack, with the text of all its files, and the results of the last check.
+#[derive(Debug)]
+pub struct Pack {
+    pub root: PathBuf,
+    /// File contents, keyed by the path relative to the pack root, separated by `/`. Open files
+    /// have their contents in the editor, other files their contents on disk.
+    pub files: BTreeMap<String, String>,
+    /// The pack loaded from `files`, if `pack.mcmeta` parses.
+    loaded: Option<DataPack>,
+    /// Diagnostics of each file on its own, i.e. all except those for references.
+    local: BTreeMap<String, Vec<Diagnostic>>,
+    /// Diagnostics for references to resources not in the pack, by the referring file.
+    references: BTreeMap<String, Vec<Diagnostic>>,
+}
+
+impl Workspace {
+    /// Find and read all data packs in `root`.
+    pub fn load(root: &Path) -> Workspace {
+        let mut packs = Vec::new();
+        if root.join("pack.mcmeta").is_file() {
+            packs.push(Pack::load(root));
+        } else if let Ok(entries) = fs::read_dir(root) {
+            let mut dirs: Vec<_> = entries.flatten().map(|entry| entry.path())
+                .filter(|path| path.join("pack.mcmeta").is_file())
+                .collect();
+            dirs.sort();
+            packs.extend(dirs.iter().map(|dir| Pack::load(dir)));
+        }
+        Workspace { packs }
+    }
+
+    /// The pack containing a file, and the path of the file relative to the pack root.
+    pub fn locate(&self, path: &Path) -> Option<(usize, String)> {
+        self.packs.iter().enumerate().find_map(|(k, pack)| {
+            let relative = path.strip_prefix(&pack.root).ok()?;
+            let parts: Option<Vec<_>> = relative.components().map(|c| c.as_os_str().to_str()).collect();
+            Some((k, parts?.join("/")))
+        })
+    }
+
+    /// Set the contents of a file, e.g. when it is opened or edited. Returns the pack containing
+    /// the file, and the path of the file relative to the pack root, if any.
+    pub fn update(&mut self, path: &Path, text: String) -> Option<(usize, String)> {
+        let (k, relative) = self.locate(path)?;
+        self.packs[k].files.insert(relative.clone(), text);
+        Some((k, relative))
+    }
+
+    /// Reset the contents of a file to that on disk, e.g. when it is closed without saving. The
+    /// file is removed if it no longer exists on disk. Returns the pack containing the file, and
+    /// the path of the file relative to the pack root, if any.
+    pub fn reset(&mut self, path: &Path) -> Option<(usize, String)> {
+        let (k, relative) = self.locate(path)?;
+        match fs::read_to_string(path) {
+            Ok(text) => self.packs[k].files.insert(relative.clone(), text),
+            Err(_) => self.packs[k].files.remove(&relative),
+        };
+        Some((k, relative))
+    }
+}
+
+impl Pack {
+    fn load(root: &Path) -> Pack {
+        let mut files = BTreeMap::new();
+        if let Ok(meta) = fs::read_to_string(root.join("pack.mcmeta")) {
+            files.insert("pack.mcmeta".to_string(), meta);
+        }
+        read_dir(root, "data", &mut files);
+        Pack {
+            root: root.to_path_buf(),
+            files,
+            loaded: None,
+            local: BTreeMap::new(),
+            references: BTreeMap::new(),
+        }
+    }
+
+    /// The `pack.mcmeta`, if it parses.
+    pub fn meta(&self) -> Option<McMeta> {
+        lenient::from_str(self.files.get("pack.mcmeta")?).ok()
+    }
+
+    /// IDs of all resources of some kind in this pack.
+    pub fn ids(&self, kind: ResourceKind) -> Vec<String> {
+        self.files.keys().filter_map(|path| ResourceKind::from_path(path))
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, id)| id)
+            .collect()
+    }
+
+    /// Check all files in this pack. Returns the diagnostics of all files, including files
+    /// without any, so that stale diagnostics are cleared.
+    pub fn check_all(&mut self) -> BTreeMap<String, Vec<Diagnostic>> {
+        self.local.clear();
+        self.references.clear();
+        let mut checker = Checker::new(&self.files);
+        self.loaded = match self.files.get("pack.mcmeta").map(|meta| lenient::from_str::<McMeta>(meta)) {
+            Some(Ok(meta)) => Some(DataPack::new(meta)),
+            Some(Err(err)) => {
+                checker.error_at("pack.mcmeta", &err.path, err.line, err.column, err.message);
+                None
+            }
+            None => None,
+        };
+        if let Some(pack) = &mut self.loaded {
+            for path in self.files.keys().filter(|path| *path != "pack.mcmeta") {
+                checker.load(pack, path);
+            }
+        }
+        self.local = checker.diagnostics;
+        self.references = self.check_references();
+        self.files.keys().map(|file| (file.clone(), self.diagnostics(file))).collect()
+    }
+
+    /// Check a file after it changed, or was deleted. Only the files defining the same resource
+    /// are loaded again. Returns the diagnostics of those files, and of files whose references
+    /// now resolve differently.
+    pub fn check_file(&mut self, file: &str) -> BTreeMap<String, Vec<Diagnostic>> {
+        let (pack, (kind, id)) = match (&mut self.loaded, ResourceKind::from_path(file)) {
+            (Some(pack), Some(resource)) if file != "pack.mcmeta" => (pack, resource),
+            (Some(_), None) if file != "pack.mcmeta" => return BTreeMap::new(),
+            _ => return self.check_all(),
+        };
+        pack.remove(kind, &id);
+        // with both the plural and the singular directory names, several files define the resource
+        let defining: Vec<_> = self.files.keys()
+            .filter(|path| ResourceKind::from_path(path).is_some_and(|res| res == (kind, id.clone())))
+            .collect();
+        let mut checker = Checker::new(&self.files);
+        for path in &defining {
+            checker.load(pack, path);
+        }
+        let mut changed: BTreeSet<String> = defining.into_iter().cloned().collect();
+        changed.insert(file.to_string());
+        for path in &changed {
+            match checker.diagnostics.remove(path) {
+                Some(diagnostics) => self.local.insert(path.clone(), diagnostics),
+                None => self.local.remove(path),
+            };
+        }
+        let references = self.check_references();
+        let files = self.references.keys().chain(references.keys());
+        changed.extend(files.filter(|file| self.references.get(*file) != references.get(*file)).cloned());
+        self.references = references;
+        changed.into_iter().map(|file| {
+            let diagnostics = self.diagnostics(&file);
+            (file, diagnostics)
+        }).collect()
+    }
+
+    fn diagnostics(&self, file: &str) -> Vec<Diagnostic> {
+        let local = self.local.get(file).into_iter().flatten();
+        local.chain(self.references.get(file).into_iter().flatten()).cloned().collect()
+    }
+
+    fn check_references(&self) -> BTreeMap<String, Vec<Diagnostic>> {
+        let pack = match &self.loaded {
+            Some(pack) => pack,
+            None => return BTreeMap::new(),
+        };
+        let mut checker = Checker::new(&self.files);
+        // vanilla resources are not part of the workspace, so references to them are not checked
+        let dangling = Validator::new().validate(pack).into_iter()
+            .filter(|reference| !reference.id.starts_with("minecraft:"));
+        for reference in dangling {
+            let message = format!("{} '{}' does not exist", reference.kind, reference.id);
+            checker.push(&reference.file, &reference.location, DiagnosticSeverity::WARNING, message);
+        }
+        checker.diagnostics
+    }
+}
+
+fn read_dir(root: &Path, relative: &str, files: &mut BTreeMap<String, String>) {
+    let entries = match fs::read_dir(root.join(relative)) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let name = match entry.file_name().into_string() {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        let path = format!("{}/{}", relative, name);
+        if entry.path().is_dir() {
+            read_dir(root, &path, files);
+        } else if let Ok(text) = fs::read_to_string(entry.path()) {
+            files.insert(path, text);
+        }
+    }
+}
+
+struct Checker<'a> {
+    files: &'a BTreeMap<String, String>,
+    spans: BTreeMap<String, Spans>,
+    diagnostics: BTreeMap<String, Vec<Diagnostic>>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(files: &'a BTreeMap<String, String>) -> Checker<'a> {
+        Checker { files, spans: BTreeMap::new(), diagnostics: BTreeMap::new() }
+    }
+
+    /// Add a file to `pack`, with diagnostics for the file on its own.
+    fn load(&mut self, pack: &mut DataPack, file: &str) {
+        self.diagnostics.entry(file.to_string()).or_default();
+        let text = match self.files.get(file) {
+            Some(text) => text,
+            None => return,
+        };
+        let mut errors = Vec::new();
+        pack.add_file(file, text, &mut errors);
+        for error in errors {
+            match error.error {
+                LoadErrorKind::Json(err) => self.error_at(file, &err.path, err.line, err.column, err.message),
+                LoadErrorKind::Version(mut issue) => {
+                    let location = std::mem::take(&mut issue.location);
+                    self.push(file, &location, DiagnosticSeverity::WARNING, issue.to_string());
+                }
+                LoadErrorKind::DuplicateKey(duplicate) => {
+                    let range = self.spans(file).key(&duplicate.path)
+                        .map_or_else(Range::default, |span| range(text, span));
+                    let message = format!("duplicate key '{}', only the last value is used", duplicate.key);
+                    self.push_range(file, range, DiagnosticSeverity::WARNING, message);
+                }
+                err => self.push(file, "", DiagnosticSeverity::ERROR, err.to_string()),
+            }
+        }
+        let advancement = match ResourceKind::from_path(file) {
+            Some((ResourceKind::Advancement, id)) => pack.advancements.get(&id),
+            _ => None,
+        };
+        for (name, criterion) in advancement.into_iter().flat_map(|advancement| &advancement.criteria) {
+            if let Criterion::Other { trigger, .. } = criterion {
+                let location = format!("criteria.{}.trigger", name);
+                self.push(file, &location, DiagnosticSeverity::WARNING, format!("unknown trigger '{}'", trigger));
+            }
+        }
+    }
+
+    fn spans(&mut self, file: &str) -> &Spans {
+        let text = self.files.get(file);
+        self.spans.entry(file.to_string()).or_insert_with(|| {
+            text.and_then(|text| lenient::parse(text).ok()).map(|parsed| parsed.spans).unwrap_or_default()
+        })
+    }
+
+    fn push(&mut self, file: &str, location: &str, severity: DiagnosticSeverity, message: String) {
+        let span = self.spans(file).find(location).map(|(_, span)| span);
+        let range = match (span, self.files.get(file)) {
+            (Some(span), Some(text)) => range(text, span),
+            _ => Range::default(),
+        };
+        self.push_range(file, range, severity, message);
+    }
+
+    /// An error located by a path if possible, or else by a line and a column.
+    fn error_at(&mut self, file: &str, location: &str, line: usize, column: usize, message: String) {
+        let text = self.files.get(file).map_or("", String::as_str);
+        let span = self.spans(file).find(location).filter(|(path, _)| !path.is_empty() || location.is_empty());
+        let range = match span {
+            Some((_, span)) if line == 0 || !location.is_empty() => range(text, span),
+            _ => {
+                let start = position(text, offset_of(text, line, column));
+                Range::new(start, start)
+            }
+        };
+        let message = if location.is_empty() { message } else { format!("{}: {}", location, message) };
+        self.push_range(file, range, DiagnosticSeverity::ERROR, message);
+    }
+
+    fn push_range(&mut self, file: &str, range: Range, severity: DiagnosticSeverity, message: String) {
+        self.diagnostics.entry(file.to_string()).or_default().push(Diagnostic {
+            range,
+            severity: Some(severity),
+            source: Some("minecraft-json".to_string()),
+            message,
+            ..Diagnostic::default()
+        });
+    }
+}
+
+/// Byte offset of a line and a column (both starting from 1, the column counted in bytes).
+fn offset_of(text: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = text.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum();
+    (line_start + column.saturating_sub(1)).min(text.len())
+}
+
+/// LSP position (zero-based, the character counted in UTF-16 code units) of a byte offset.
+pub fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |k| k + 1);
+    let line = before.matches('\n').count() as u32;
+    Position::new(line, before[line_start..].encode_utf16().count() as u32)
+}
+
+/// Byte offset of an LSP position, clamped to the line.
+pub fn offset(text: &str, position: Position) -> usize {
+    let line_start: usize = text.split_inclusive('\n').take(position.line as usize).map(str::len).sum();
+    let mut units = 0;
+    for (k, c) in text[line_start..].char_indices() {
+        if units >= position.character as usize || c == '\n' {
+            return line_start + k;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// LSP range of a span.
+pub fn range(text: &str, span: Span) -> Range {
+    Range::new(position(text, span.start), position(text, span.end))
+}
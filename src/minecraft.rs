@@ -21,3 +21,6 @@
 pub mod common;
 pub mod text;
 pub mod data;
+pub mod error;
+pub mod lenient;
+pub mod span;
@@ -19,12 +19,18 @@
 //! Common data types for use in Minecraft JSON.
 
 use derivative::Derivative;
-use serde::{Serialize, Deserialize};
-use serde_json::Number;
+use serde::{Serialize, Deserialize, Deserializer};
+use serde::de::DeserializeOwned;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::{Map, Number, Value};
+use serde_json::value::RawValue;
+use crate::minecraft::error;
 
 /// Union of two JSON deserializable objects.
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Derivative, Serialize, Deserialize)]
+#[derive(Derivative, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[derivative(Default(bound = "L: Default"))]
 #[serde(untagged)]
 pub enum Either<L, R> {
@@ -35,9 +41,19 @@ pub enum Either<L, R> {
     Right(R),
 }
 
+impl<'de, L: DeserializeOwned, R: DeserializeOwned> Deserialize<'de> for Either<L, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        error::untagged(deserializer, "Either", &[
+            &|v| error::variant(v, Either::Left),
+            &|v| error::variant(v, Either::Right),
+        ])
+    }
+}
+
 /// A 3D vector, for positions, velocities, etc.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Derivative, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[derivative(Default(bound = ""))]
 #[allow(missing_docs)]
 pub struct Vector3d<I> {
@@ -54,7 +70,8 @@ pub type Ranged<I> = Ranged2<I, I>;
 
 /// Ranged values for use in conditions, with possibly different types for exact and ranged values.
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Derivative, Serialize, Deserialize)]
+#[derive(Derivative, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[derivative(Default)]
 #[serde(untagged)]
 #[allow(missing_docs)]
@@ -69,9 +86,27 @@ pub enum Ranged2<I, N> {
     },
 }
 
+impl<'de, I: DeserializeOwned, N: DeserializeOwned> Deserialize<'de> for Ranged2<I, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(bound = "N: DeserializeOwned")]
+        struct Range<N> {
+            #[serde(default)]
+            min: Option<N>,
+            #[serde(default)]
+            max: Option<N>,
+        }
+        error::untagged(deserializer, "Ranged2", &[
+            &|v| error::variant(v, Ranged2::Exact),
+            &|v| error::variant(v, |Range { min, max }| Ranged2::Range { min, max }),
+        ])
+    }
+}
+
 /// Plain values for use in block states or fluid states.
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 #[allow(missing_docs)]
 pub enum PlainValue {
@@ -80,21 +115,52 @@ pub enum PlainValue {
     String(String),
 }
 
+impl<'de> Deserialize<'de> for PlainValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        error::untagged(deserializer, "PlainValue", &[
+            &|v| error::variant(v, PlainValue::Boolean),
+            &|v| error::variant(v, PlainValue::Integer),
+            &|v| error::variant(v, PlainValue::String),
+        ])
+    }
+}
+
 /// Constant number provider as raw number or JSON object.
+///
+/// ```
+/// # use minecraft_json::minecraft::{data::loot_table::LootTable, error};
+/// let json = r#"{"pools": [{"rolls": {"type": "constant"}, "entries": []}]}"#;
+/// let err = error::from_str::<LootTable>(json).unwrap_err();
+/// assert_eq!(err.to_string(), "pools[0].rolls: missing field `value` at line 1 column 22");
+/// ```
 pub type NumberProviderValue<I> = Either<I, Box<NumberProvider<I>>>;
 
 /// Loot tables use number providers in some places that accept an int or float. They can either
-/// be defined as a constant value or as an object.
+/// be defined as a constant value or as an object. The `minecraft` namespace of the type may be
+/// omitted.
+///
+/// ```
+/// # use minecraft_json::minecraft::common::{Either, NumberProvider};
+/// let json = r#"{"type": "minecraft:uniform", "min": 1, "max": {"type": "constant", "value": 3}}"#;
+/// let provider: NumberProvider<i32> = serde_json::from_str(json).unwrap();
+/// assert_eq!(provider, NumberProvider::Uniform {
+///     min: Box::new(Either::Left(1)),
+///     max: Box::new(Either::Right(Box::new(NumberProvider::Constant { value: 3 }))),
+/// });
+/// ```
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case", bound(deserialize = "I: DeserializeOwned"))]
 pub enum NumberProvider<I> {
     /// A constant value.
+    #[serde(alias = "minecraft:constant")]
     Constant {
         /// The exact value.
         value: I,
     },
     /// A random number following a uniform distribution between two values (inclusive).
+    #[serde(alias = "minecraft:uniform")]
     Uniform {
         /// Number provider. The minimum value.
         min: Box<NumberProviderValue<I>>,
@@ -102,6 +168,7 @@ pub enum NumberProvider<I> {
         max: Box<NumberProviderValue<I>>,
     },
     /// A random number following a binomial distribution.
+    #[serde(alias = "minecraft:binomial")]
     Binomial {
         /// Number provider. The amount of trials.
         min: Box<NumberProviderValue<isize>>,
@@ -109,6 +176,7 @@ pub enum NumberProvider<I> {
         max: Box<NumberProviderValue<Number>>,
     },
     /// A scoreboard value.
+    #[serde(alias = "minecraft:score")]
     Score {
         /// Scoreboard name provider.
         target: ScoreboardNameProvider,
@@ -126,6 +194,7 @@ pub type ScoreboardNameProvider = Either<ScoreboardName, ScoreboardSelector>;
 /// Scoreboard name provider.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 #[allow(missing_docs)]
 pub enum ScoreboardName {
@@ -138,15 +207,335 @@ pub enum ScoreboardName {
 /// Scoreboard name provider.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[allow(missing_docs)]
 pub enum ScoreboardSelector {
+    #[serde(alias = "minecraft:fixed")]
     Fixed {
         /// A UUID or playername.
         name: String,
     },
+    #[serde(alias = "minecraft:context")]
     Context {
         /// Scoreboard name provider.
         target: ScoreboardName,
     },
 }
+
+/// Raw JSON text, kept verbatim for lossless round trips. Equality compares the text.
+#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RawJson(pub Box<RawValue>);
+
+#[cfg(feature = "schema")]
+impl JsonSchema for RawJson {
+    fn schema_name() -> String { "RawJson".to_string() }
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::Schema::Bool(true)
+    }
+}
+
+impl RawJson {
+    /// The raw JSON text.
+    pub fn get(&self) -> &str { self.0.get() }
+}
+
+impl PartialEq for RawJson {
+    fn eq(&self, other: &Self) -> bool { self.get() == other.get() }
+}
+
+impl Eq for RawJson {}
+
+/// Fields of a JSON object not known to this crate, e.g. a misspelled field, or a field from a
+/// newer version of the game. They are kept, and written back out after the known fields, so
+/// that loading and saving a resource loses nothing. In [`strict`](error::strict) mode, they are
+/// rejected instead.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::conditions::Entity;
+/// # use minecraft_json::minecraft::error;
+/// let json = r#"{"team":"red","tem":"blue"}"#;
+/// let entity: Entity = serde_json::from_str(json).unwrap();
+/// assert_eq!(entity.extra.0["tem"], "blue");
+/// assert_eq!(serde_json::to_string(&entity).unwrap(), json);
+///
+/// let err = error::strict(|| error::from_str::<Entity>(json)).unwrap_err();
+/// assert_eq!(err.to_string(), "tem: unknown field at line 1 column 21");
+/// ```
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(transparent)]
+pub struct Extra(pub Map<String, Value>);
+
+impl Extra {
+    /// Are there no unknown fields?
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let extra = Map::deserialize(deserializer)?;
+        match extra.keys().next() {
+            Some(key) if error::is_strict() => Err(error::unknown_field(key)),
+            _ => Ok(Extra(extra)),
+        }
+    }
+}
+
+/// A namespaced ID, e.g. `minecraft:stone`, where the namespace defaults to `minecraft`.
+///
+/// The namespace may only contain `a-z`, `0-9`, `_`, `-` and `.`, and the path may also contain
+/// `/`. The ID is kept as written for serialisation, but compares equal to its fully namespaced
+/// form, and is displayed as such.
+///
+/// ```
+/// # use minecraft_json::minecraft::common::ResourceLocation;
+/// let stone: ResourceLocation = "stone".parse().unwrap();
+/// assert_eq!(stone.namespace(), "minecraft");
+/// assert_eq!(stone.path(), "stone");
+/// assert_eq!(stone, "minecraft:stone".parse().unwrap());
+/// assert_eq!(stone.to_string(), "minecraft:stone");
+/// assert_eq!(serde_json::to_string(&stone).unwrap(), r#""stone""#);
+/// assert!("minecraft:Stone".parse::<ResourceLocation>().is_err());
+/// assert!("my pack:foo".parse::<ResourceLocation>().is_err());
+/// ```
+#[derive(Clone)]
+pub struct ResourceLocation {
+    raw: String,
+    colon: Option<usize>,
+}
+
+/// Error for invalid [`ResourceLocation`]s.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ResourceLocationError {
+    /// The invalid input.
+    pub input: String,
+    /// The first invalid character.
+    pub character: char,
+    /// Byte offset of the first invalid character.
+    pub position: usize,
+}
+
+impl std::fmt::Display for ResourceLocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid character {:?} at position {} in resource location '{}'",
+               self.character, self.position, self.input)
+    }
+}
+
+impl std::error::Error for ResourceLocationError {}
+
+impl ResourceLocation {
+    /// Create a resource location from a namespace and a path.
+    pub fn new(namespace: &str, path: &str) -> Result<ResourceLocation, ResourceLocationError> {
+        format!("{}:{}", namespace, path).parse()
+    }
+
+    /// The namespace, `minecraft` if omitted.
+    pub fn namespace(&self) -> &str {
+        match self.colon {
+            None | Some(0) => "minecraft",
+            Some(k) => &self.raw[..k],
+        }
+    }
+
+    /// The path.
+    pub fn path(&self) -> &str {
+        self.colon.map_or(&self.raw, |k| &self.raw[k + 1..])
+    }
+
+    /// The ID as written, possibly without a namespace.
+    pub fn as_str(&self) -> &str { &self.raw }
+
+    /// Add the default `minecraft` namespace to an ID if it has none. Unlike parsing, this does
+    /// not validate the ID.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::common::ResourceLocation;
+    /// assert_eq!(ResourceLocation::normalise("stone"), "minecraft:stone");
+    /// assert_eq!(ResourceLocation::normalise("demo:Stone"), "demo:Stone");
+    /// ```
+    pub fn normalise(id: &str) -> String {
+        if id.contains(':') { id.to_string() } else { format!("minecraft:{}", id) }
+    }
+}
+
+impl std::str::FromStr for ResourceLocation {
+    type Err = ResourceLocationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colon = s.find(':');
+        let valid_namespace = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.');
+        let invalid = s.char_indices().find(|&(k, c)| match colon {
+            Some(colon) if k < colon => !valid_namespace(c),
+            Some(colon) if k == colon => false,
+            _ => !(valid_namespace(c) || c == '/'),
+        });
+        match invalid {
+            Some((position, character)) =>
+                Err(ResourceLocationError { input: s.to_string(), character, position }),
+            None => Ok(ResourceLocation { raw: s.to_string(), colon }),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl JsonSchema for ResourceLocation {
+    fn schema_name() -> String { "ResourceLocation".to_string() }
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        string_schema(r"^([a-z0-9_.-]+:)?[a-z0-9_./-]+$",
+                      "Namespaced ID, in the default `minecraft` namespace if there is no colon.")
+    }
+}
+
+impl std::fmt::Display for ResourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.namespace(), self.path())
+    }
+}
+
+impl std::fmt::Debug for ResourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.raw)
+    }
+}
+
+impl PartialEq for ResourceLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace() == other.namespace() && self.path() == other.path()
+    }
+}
+
+impl Eq for ResourceLocation {}
+
+impl PartialOrd for ResourceLocation {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for ResourceLocation {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.namespace(), self.path()).cmp(&(other.namespace(), other.path()))
+    }
+}
+
+impl std::hash::Hash for ResourceLocation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.namespace().hash(state);
+        self.path().hash(state);
+    }
+}
+
+impl Serialize for ResourceLocation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResourceLocation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Either a tag (written with a leading `#`) or a single ID, for fields accepting both.
+///
+/// ```
+/// # use minecraft_json::minecraft::common::TagOrId;
+/// let logs: TagOrId = serde_json::from_str(r##""#minecraft:logs""##).unwrap();
+/// assert!(matches!(&logs, TagOrId::Tag(tag) if tag.path() == "logs"));
+/// assert_eq!(logs.to_string(), "#minecraft:logs");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Clone)]
+pub enum TagOrId {
+    /// A tag, written with a leading `#`.
+    Tag(ResourceLocation),
+    /// A single ID.
+    Id(ResourceLocation),
+}
+
+/// A tag (written with a leading `#`), a single ID, or a list of IDs, e.g. for `items` in item
+/// conditions since Minecraft 1.20.5.
+///
+/// ```
+/// # use minecraft_json::minecraft::common::{Either, TagOrId, TagOrIds};
+/// let axes: TagOrIds = serde_json::from_str(r##""#minecraft:axes""##).unwrap();
+/// assert!(matches!(&axes, Either::Left(TagOrId::Tag(tag)) if tag.path() == "axes"));
+/// let items: TagOrIds = serde_json::from_str(r#"["stick", "minecraft:bone"]"#).unwrap();
+/// assert!(matches!(&items, Either::Right(ids) if ids.len() == 2));
+/// ```
+pub type TagOrIds = Either<TagOrId, Vec<ResourceLocation>>;
+
+impl TagOrId {
+    /// The tag or the ID, without the leading `#`.
+    pub fn location(&self) -> &ResourceLocation {
+        match self {
+            TagOrId::Tag(location) | TagOrId::Id(location) => location,
+        }
+    }
+
+    /// Whether or not this is a tag.
+    pub fn is_tag(&self) -> bool { matches!(self, TagOrId::Tag(_)) }
+}
+
+impl std::str::FromStr for TagOrId {
+    type Err = ResourceLocationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('#') {
+            Some(tag) => tag.parse().map(TagOrId::Tag).map_err(|err| ResourceLocationError {
+                input: s.to_string(),
+                position: err.position + 1,
+                ..err
+            }),
+            None => s.parse().map(TagOrId::Id),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl JsonSchema for TagOrId {
+    fn schema_name() -> String { "TagOrId".to_string() }
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        string_schema(r"^#?([a-z0-9_.-]+:)?[a-z0-9_./-]+$",
+                      "Namespaced ID, or a tag if prefixed with `#`.")
+    }
+}
+
+impl std::fmt::Display for TagOrId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TagOrId::Tag(tag) => write!(f, "#{}", tag),
+            TagOrId::Id(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl Serialize for TagOrId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TagOrId::Tag(tag) => serializer.serialize_str(&format!("#{}", tag.as_str())),
+            TagOrId::Id(id) => id.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TagOrId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Schema for strings matching a pattern, for types with custom (de)serialisation.
+#[cfg(feature = "schema")]
+pub(crate) fn string_schema(pattern: &str, description: &str) -> schemars::schema::Schema {
+    use schemars::schema::{InstanceType, Metadata, SchemaObject, StringValidation};
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation { pattern: Some(pattern.to_string()), ..Default::default() })),
+        metadata: Some(Box::new(Metadata { description: Some(description.to_string()), ..Default::default() })),
+        ..Default::default()
+    }.into()
+}
@@ -22,3 +22,15 @@ pub mod meta;
 pub mod advancement;
 pub mod conditions;
 pub mod predicate;
+pub mod extension;
+pub mod loot_table;
+pub mod item_modifier;
+pub mod recipe;
+pub mod tag;
+pub mod pack;
+pub mod validate;
+pub mod version;
+pub mod upgrade;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod registry;
@@ -22,8 +22,9 @@
 //! ```
 //! # use maplit::btreemap;
 //! # use minecraft_json::assert_equiv_pretty;
-//! # use minecraft_json::minecraft::data::advancement::{Advancement, Display, Icon, Frame, Criterion};
+//! # use minecraft_json::minecraft::data::advancement::{Advancement, Display, Icon, Frame, Criterion, TriggerSpelling};
 //! # use minecraft_json::minecraft::text::{TextComponent, TextComponentTags};
+//! # use minecraft_json::minecraft::common::Extra;
 //! assert_equiv_pretty!(r#"{
 //!   "display": {
 //!     "icon": {
@@ -49,11 +50,12 @@
 //!     ]
 //!   ]
 //! }"#, Advancement {
-//!     parent: Some("minecraft:adventure/root".to_string()),
+//!     parent: Some("minecraft:adventure/root".parse().unwrap()),
 //!     display: Some(Box::new(Display {
 //!         icon: Some(Icon {
-//!             item: "minecraft:red_bed".to_string(),
+//!             item: "minecraft:red_bed".parse().unwrap(),
 //!             nbt: None,
+//!             extra: Extra::default(),
 //!         }),
 //!         title: TextComponent::Translated {
 //!             translate: "advancements.adventure.sleep_in_bed.title".to_string(),
@@ -70,28 +72,44 @@
 //!         show_toast: true,
 //!         announce_to_chat: true,
 //!         hidden: false,
+//!         extra: Extra::default(),
 //!     })),
 //!     criteria: btreemap!{
 //!         "slept_in_bed".to_string() => Criterion::SleptInBed {
 //!             location: None,
 //!             player: None,
+//!             spelling: TriggerSpelling::Namespaced,
+//!             extra: Extra::default(),
 //!         },
 //!     },
 //!     requirements: vec![vec!["slept_in_bed".to_string()]],
 //!     rewards: None,
+//!     extra: Extra::default(),
 //! });
 //! ```
 
 use std::collections::BTreeMap;
 use derivative::Derivative;
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::de::Error;
+use serde::ser::{Error as _, SerializeStruct};
+use serde_json::{Map, Number, Value};
 use crate::defaults;
+use crate::minecraft::common::{Ranged, PlainValue, RawJson, ResourceLocation, Extra};
 use crate::minecraft::text::TextComponent;
-use crate::minecraft::data::conditions::{Location, Item, PredicatesOrEntity, Entity};
+use crate::minecraft::error;
+use crate::minecraft::data::extension::{self, CustomCondition};
+use crate::minecraft::data::conditions::{Location, Item, PredicatesOrEntity, Effect, Distance, Damage, DamageSource};
+
+pub mod tree;
+pub mod progress;
 
 /// An advancement JSON file.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Advancement {
     /// The optional display data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -99,7 +117,7 @@ pub struct Advancement {
     /// The optional parent advancement directory of this advancement. If this field is absent,
     /// this advancement is a root advancement. Circular references cause a loading failure.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub parent: Option<String>,
+    pub parent: Option<ResourceLocation>,
     /// The required criteria that have to be met.
     ///
     /// - Key: A name given to the criterion (can be any string, must be unique).
@@ -107,6 +125,7 @@ pub struct Advancement {
     ///   - `trigger`: The trigger and conditions for this advancement; specifies what the game
     ///     should check for the advancement.
     ///   - `conditions`: All the conditions that need to be met when the trigger gets activated.
+    #[cfg_attr(feature = "schema", schemars(with = "BTreeMap<String, CriterionSchema>"))]
     pub criteria: BTreeMap<String, Criterion>,
     /// An optional list of requirements (all the `<criteriaNames>`). If all criteria are required,
     /// this may be omitted. With multiple criteria: requirements contains a list of lists with
@@ -117,11 +136,15 @@ pub struct Advancement {
     /// An optional object representing the rewards provided when this advancement is obtained.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rewards: Option<Rewards>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Display data for an [`Advancement`].
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Display {
     /// The data for the icon.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -134,7 +157,7 @@ pub struct Display {
     /// The optional directory for the background to use in this advancement tab
     /// (used only for the root advancement).
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub background: Option<String>,
+    pub background: Option<ResourceLocation>,
     /// The description of the advancement.
     pub description: TextComponent,
     /// Whether or not to show the toast pop up after completing this advancement.
@@ -150,22 +173,30 @@ pub struct Display {
     /// themselves, but still affects all their children. Defaults to `false`.
     #[serde(default, skip_serializing_if = "defaults::is_false")]
     pub hidden: bool,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// An item (with NBT data) as icon.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Icon {
     /// The item id.
-    pub item: String,
+    pub item: ResourceLocation,
     /// The nbt data of the item.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nbt: Option<String>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Type of frame for the icon.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Derivative, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[derivative(Default)]
 #[serde(rename_all = "snake_case")]
 pub enum Frame {
@@ -180,10 +211,26 @@ pub enum Frame {
 
 /// Strongly-typed `trigger` and `conditions` for a criterion.
 ///
-/// TODO: add all triggers here.
+/// Unknown fields in the conditions of vanilla triggers are kept in `extra`, see [`Extra`]:
+///
+/// ```
+/// # use minecraft_json::minecraft::data::advancement::Criterion;
+/// # use minecraft_json::minecraft::error;
+/// let json = r#"{"trigger":"minecraft:inventory_changed","conditions":{"bogus":1}}"#;
+/// let criterion: Criterion = serde_json::from_str(json).unwrap();
+/// match &criterion {
+///     Criterion::InventoryChanged { extra, .. } => assert_eq!(extra.0["bogus"], 1),
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(serde_json::to_string(&criterion).unwrap(), json);
+///
+/// let err = error::strict(|| error::from_str::<Criterion>(json)).unwrap_err();
+/// assert_eq!(err.to_string(), "conditions.bogus: unknown field at line 1 column 64");
+/// ```
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Derivative, Serialize, Deserialize)]
-#[serde(tag = "trigger", content = "conditions")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "trigger", content = "conditions", remote = "Self")]
 #[non_exhaustive]
 pub enum Criterion {
     /// Triggers when the player breaks a bee nest or beehive.
@@ -191,7 +238,8 @@ pub enum Criterion {
     /// ```
     /// # use minecraft_json::assert_equiv_pretty;
     /// # use minecraft_json::minecraft::data::conditions::Item;
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
     /// assert_equiv_pretty!(r#"{
     ///   "trigger": "minecraft:bee_nest_destroyed",
     ///   "conditions": {
@@ -204,20 +252,22 @@ pub enum Criterion {
     ///     "num_bees_inside": 3
     ///   }
     /// }"#, Criterion::BeeNestDestroyed {
-    ///     block: Some("minecraft:beehive".to_string()),
+    ///     block: Some("minecraft:beehive".parse().unwrap()),
     ///     item: Some(Box::new(Item {
-    ///         items: vec!["minecraft:wooden_axe".to_string()],
+    ///         items: Some(Either::Right(vec!["minecraft:wooden_axe".parse().unwrap()])),
     ///         ..Item::default()
     ///     })),
     ///     num_bees_inside: Some(3),
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:bee_nest_destroyed")]
     BeeNestDestroyed {
         /// The block that was destroyed. Accepts block IDs.
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        block: Option<String>,
+        block: Option<ResourceLocation>,
         /// The item used to break the block. See also [`Item`].
         #[serde(default, skip_serializing_if = "Option::is_none")]
         item: Option<Box<Item>>,
@@ -228,6 +278,12 @@ pub enum Criterion {
         /// pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Triggers after the player breeds 2 animals.
     ///
@@ -235,8 +291,8 @@ pub enum Criterion {
     /// # use maplit::btreemap;
     /// # use minecraft_json::assert_equiv_pretty_protected;
     /// # use minecraft_json::minecraft::data::conditions::{Item, Entity, Location, Effect};
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
-    /// # use minecraft_json::minecraft::common::{Ranged, Either};
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Ranged, Either, Extra};
     /// assert_equiv_pretty_protected!(r#"{
     ///   "trigger": "minecraft:bred_animals",
     ///   "conditions": {
@@ -260,19 +316,19 @@ pub enum Criterion {
     ///   }
     /// }"#, Criterion::BredAnimals {
     ///     child: Some(Either::Right(Box::new(Entity {
-    ///         r#type: Some("minecraft:mule".to_string()),
+    ///         r#type: Some("minecraft:mule".parse().unwrap()),
     ///         ..Entity::default()
     ///     }))),
     ///     parent: Some(Either::Right(Box::new(Entity {
     ///         location: Some(Box::new(Location {
-    ///             biome: Some("minecraft:beach".to_string()),
+    ///             biome: Some("minecraft:beach".parse().unwrap()),
     ///             ..Location::default()
     ///         })),
     ///         ..Entity::default()
     ///     }))),
     ///     partner: Some(Either::Right(Box::new(Entity {
     ///         effects: btreemap! {
-    ///             "minecraft:speed".to_string() => Effect {
+    ///             "minecraft:speed".parse().unwrap() => Effect {
     ///                 amplifier: Some(Ranged::Range {
     ///                     min: Some(2),
     ///                     max: None,
@@ -283,6 +339,8 @@ pub enum Criterion {
     ///         ..Entity::default()
     ///     }))),
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:bred_animals")]
@@ -303,37 +361,53 @@ pub enum Criterion {
         /// pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Triggers after the player takes any item out of a brewing stand.
     ///
     /// ```
     /// # use minecraft_json::assert_equiv_pretty;
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
     /// assert_equiv_pretty!(r#"{
     ///   "trigger": "minecraft:brewed_potion",
     ///   "conditions": {
     ///     "potion": "minecraft:strong_swiftness"
     ///   }
     /// }"#, Criterion::BrewedPotion {
-    ///     potion: Some("minecraft:strong_swiftness".to_string()),
+    ///     potion: Some("minecraft:strong_swiftness".parse().unwrap()),
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:brewed_potion")]
     BrewedPotion {
         /// A brewed potion ID.
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        potion: Option<String>,
+        potion: Option<ResourceLocation>,
         /// The player that would get the advancement. May also be a list of predicates that must
         /// pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Triggers after the player travels between two dimensions.
     ///
     /// ```
     /// # use minecraft_json::assert_equiv_pretty;
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
     /// assert_equiv_pretty!(r#"{
     ///   "trigger": "minecraft:changed_dimension",
     ///   "conditions": {
@@ -341,29 +415,38 @@ pub enum Criterion {
     ///     "to": "minecraft:overworld"
     ///   }
     /// }"#, Criterion::ChangedDimension {
-    ///     from: Some("minecraft:the_end".to_string()),
-    ///     to: Some("minecraft:overworld".to_string()),
+    ///     from: Some("minecraft:the_end".parse().unwrap()),
+    ///     to: Some("minecraft:overworld".parse().unwrap()),
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:changed_dimension")]
     ChangedDimension {
         /// The dimension the entity traveled from. Accepts these 3 values.
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        from: Option<String>,
+        from: Option<ResourceLocation>,
         /// The dimension the entity traveled to. Same accepted values as above.
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        to: Option<String>,
+        to: Option<ResourceLocation>,
         /// The player that would get the advancement. May also be a list of predicates that must
         /// pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Triggers after the player successfully uses the Channeling enchantment on an entity.
     ///
     /// ```
     /// # use minecraft_json::assert_equiv_pretty;
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
     /// use minecraft_json::minecraft::data::conditions::Entity;
     /// assert_equiv_pretty!(r#"{
     ///   "trigger": "minecraft:channeled_lightning",
@@ -376,12 +459,14 @@ pub enum Criterion {
     ///     ]
     ///   }
     /// }"#, Criterion::ChanneledLightning {
-    ///     victims: vec![Entity {
-    ///         r#type: Some("minecraft:skeleton_horse".to_string()),
+    ///     victims: vec![Either::Right(Box::new(Entity {
+    ///         r#type: Some("minecraft:skeleton_horse".parse().unwrap()),
     ///         nbt: Some("{SkeletonTrap: true}".to_string()),
     ///         ..Entity::default()
-    ///     }],
+    ///     }))],
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:channeled_lightning")]
@@ -391,19 +476,811 @@ pub enum Criterion {
         /// in order for the trigger to activate. The checks are applied to the victim hit by the
         /// enchanted trident.
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
-        victims: Vec<Entity>,
+        victims: Vec<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player changes the structure of a beacon. (When the beacon updates
+    /// itself).
+    #[serde(rename = "minecraft:construct_beacon")]
+    ConstructBeacon {
+        /// The level of the updated beacon structure.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level: Option<Ranged<isize>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when a player consumes an item.
+    #[serde(rename = "minecraft:consume_item")]
+    ConsumeItem {
+        /// The item that was consumed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player cures a zombie villager.
+    #[serde(rename = "minecraft:cured_zombie_villager")]
+    CuredZombieVillager {
+        /// The villager that is the result of the conversion. May also be a list of predicates
+        /// that must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        villager: Option<PredicatesOrEntity>,
+        /// The zombie villager right before the conversion is complete. May also be a list of
+        /// predicates that must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        zombie: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player gets a status effect applied or taken from them.
+    #[serde(rename = "minecraft:effects_changed")]
+    EffectsChanged {
+        /// A list of active status effects the player currently has.
+        ///
+        /// - Key: The status effect ID.
+        /// - Value: See [`Effect`].
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        effects: BTreeMap<ResourceLocation, Effect>,
+        /// The entity that was the source of the status effect. May also be a list of predicates
+        /// that must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player enchants an item through an enchanting table (does not get
+    /// triggered through an anvil, or through commands).
+    #[serde(rename = "minecraft:enchanted_item")]
+    EnchantedItem {
+        /// The item after it has been enchanted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The levels spent by the player on the enchantment.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        levels: Option<Ranged<isize>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player stands in a block. Checks every tick and tries to match one time
+    /// for each block the player is standing in.
+    #[serde(rename = "minecraft:enter_block")]
+    EnterBlock {
+        /// The block that the player is standing in. Accepts block IDs.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<ResourceLocation>,
+        /// Block states to match.
+        ///
+        /// - Key: A block state and its value.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        state: BTreeMap<String, PlainValue>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after a player gets hurt.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::conditions::{Damage, DamageSource};
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:entity_hurt_player",
+    ///   "conditions": {
+    ///     "damage": {
+    ///       "blocked": true,
+    ///       "type": {
+    ///         "is_projectile": true
+    ///       }
+    ///     }
+    ///   }
+    /// }"#, Criterion::EntityHurtPlayer {
+    ///     damage: Some(Box::new(Damage {
+    ///         blocked: Some(true),
+    ///         r#type: Some(Box::new(DamageSource {
+    ///             is_projectile: Some(true),
+    ///             ..DamageSource::default()
+    ///         })),
+    ///         ..Damage::default()
+    ///     })),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:entity_hurt_player")]
+    EntityHurtPlayer {
+        /// Checks the damage done to the player.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        damage: Option<Box<Damage>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after a living entity kills a player.
+    #[serde(rename = "minecraft:entity_killed_player")]
+    EntityKilledPlayer {
+        /// Checks the entity that was the source of the damage that killed the player (for
+        /// example: The skeleton that shot the arrow). May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// Checks the type of damage that killed the player.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        killing_blow: Option<Box<DamageSource>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player fills a bucket.
+    #[serde(rename = "minecraft:filled_bucket")]
+    FilledBucket {
+        /// The item resulting from filling the bucket.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player successfully catches an item with a fishing rod or pulls an
+    /// entity with a fishing rod.
+    #[serde(rename = "minecraft:fishing_rod_hooked")]
+    FishingRodHooked {
+        /// The entity that was pulled, or the fishing bobber if no entity is pulled. May also be
+        /// a list of predicates that must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The item that was caught.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The fishing rod used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rod: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when a raid ends in victory and the player has attacked at least one raider from
+    /// that raid.
+    #[serde(rename = "minecraft:hero_of_the_village")]
+    HeroOfTheVillage {
+        /// The location of the player.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<Box<Location>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player collides with a honey block.
+    #[serde(rename = "minecraft:honey_block_slide")]
+    HoneyBlockSlide {
+        /// The block that the player collided with. Accepts block IDs.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<ResourceLocation>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Never triggers. Used for advancements granted only by commands, and for recipe
+    /// advancements that should not be unlocked automatically.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// let criterion: Criterion = serde_json::from_str(r#"{"trigger": "minecraft:impossible"}"#).unwrap();
+    /// assert_eq!(criterion, Criterion::Impossible { spelling: TriggerSpelling::Namespaced, extra: Extra::default() });
+    /// ```
+    #[serde(rename = "minecraft:impossible")]
+    Impossible {
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after any changes happen to the player's inventory.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty_protected;
+    /// # use minecraft_json::minecraft::data::conditions::Item;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, InventorySlots, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Ranged, Extra};
+    /// assert_equiv_pretty_protected!(r#"{
+    ///   "trigger": "minecraft:inventory_changed",
+    ///   "conditions": {
+    ///     "items": [
+    ///       {
+    ///         "items": [
+    ///           "minecraft:cobblestone"
+    ///         ]
+    ///       }
+    ///     ],
+    ///     "slots": {
+    ///       "empty": {
+    ///         "max": 3
+    ///       }
+    ///     }
+    ///   }
+    /// }"#, Criterion::InventoryChanged {
+    ///     items: vec![Item {
+    ///         items: Some(Either::Right(vec!["minecraft:cobblestone".parse().unwrap()])),
+    ///         ..Item::default()
+    ///     }],
+    ///     slots: Some(InventorySlots {
+    ///         empty: Some(Ranged::Range { min: None, max: Some(3) }),
+    ///         ..InventorySlots::default()
+    ///     }),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:inventory_changed")]
+    InventoryChanged {
+        /// A list of items in the player's inventory. All items in the list must be in the
+        /// player's inventory, but not all items in the player's inventory have to be in this
+        /// list.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        items: Vec<Item>,
+        /// Checks the number of slots in the player's inventory.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        slots: Option<InventorySlots>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after any item in the inventory has been damaged in any form.
+    #[serde(rename = "minecraft:item_durability_changed")]
+    ItemDurabilityChanged {
+        /// The change in durability (negative numbers are used to indicate a decrease in
+        /// durability).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delta: Option<Ranged<isize>>,
+        /// The remaining durability of the item.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        durability: Option<Ranged<isize>>,
+        /// The item before it was damaged, allows you to check the durability before the item
+        /// was damaged.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
         /// The player that would get the advancement. May also be a list of predicates that must
         /// pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player uses their hand or an item on a block.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::conditions::{Block, Item, Location};
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:item_used_on_block",
+    ///   "conditions": {
+    ///     "location": {
+    ///       "block": {
+    ///         "tag": "minecraft:beehives"
+    ///       }
+    ///     },
+    ///     "item": {
+    ///       "items": [
+    ///         "minecraft:glass_bottle"
+    ///       ]
+    ///     }
+    ///   }
+    /// }"#, Criterion::ItemUsedOnBlock {
+    ///     location: Some(Box::new(Location {
+    ///         block: Some(Box::new(Block {
+    ///             tag: Some("minecraft:beehives".parse().unwrap()),
+    ///             ..Block::default()
+    ///         })),
+    ///         ..Location::default()
+    ///     })),
+    ///     item: Some(Box::new(Item {
+    ///         items: Some(Either::Right(vec!["minecraft:glass_bottle".parse().unwrap()])),
+    ///         ..Item::default()
+    ///     })),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:item_used_on_block")]
+    ItemUsedOnBlock {
+        /// The location at the center of the block the item was used on.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<Box<Location>>,
+        /// The item used on the block.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player kills a mob or player using a crossbow in ranged combat.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty_protected;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Ranged, Extra};
+    /// assert_equiv_pretty_protected!(r#"{
+    ///   "trigger": "minecraft:killed_by_crossbow",
+    ///   "conditions": {
+    ///     "unique_entity_types": {
+    ///       "min": 5
+    ///     }
+    ///   }
+    /// }"#, Criterion::KilledByCrossbow {
+    ///     unique_entity_types: Some(Ranged::Range { min: Some(5), max: None }),
+    ///     victims: Vec::new(),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:killed_by_crossbow")]
+    KilledByCrossbow {
+        /// The exact count of types of entities killed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unique_entity_types: Option<Ranged<isize>>,
+        /// A list of victims. All of the entries must be matched, and one killed entity may match
+        /// only one entry. Each entry may also be a list of predicates that must pass in order for
+        /// the trigger to activate.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        victims: Vec<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player has the levitation status effect.
+    #[serde(rename = "minecraft:levitation")]
+    Levitation {
+        /// The distance between the position where the player started levitating and the
+        /// player's current position.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<Box<Distance>>,
+        /// The duration of the levitation in ticks.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration: Option<Ranged<isize>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when a lightning bolt disappears from the world, only for players within a
+    /// 256 block distance of the lightning bolt.
+    #[serde(rename = "minecraft:lightning_strike")]
+    LightningStrike {
+        /// The lightning bolt that disappeared. May also be a list of predicates that must pass
+        /// in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lightning: Option<PredicatesOrEntity>,
+        /// An entity not hurt by the lightning strike but in a certain area around it. May also
+        /// be a list of predicates that must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bystander: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers every 20 ticks (1 second) and checks where the player is.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::conditions::Location;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:location",
+    ///   "conditions": {
+    ///     "location": {
+    ///       "feature": "fortress"
+    ///     }
+    ///   }
+    /// }"#, Criterion::Location {
+    ///     location: Some(Box::new(Location {
+    ///         feature: Some("fortress".to_string()),
+    ///         ..Location::default()
+    ///     })),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:location")]
+    Location {
+        /// The location of the player.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<Box<Location>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player travels to the Nether and then returns to the Overworld.
+    #[serde(rename = "minecraft:nether_travel")]
+    NetherTravel {
+        /// The location where the player entered the Nether.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entered: Option<Box<Location>>,
+        /// The location where the player exited the Nether.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exited: Option<Box<Location>>,
+        /// The overworld distance between where the player entered the Nether and where the
+        /// player exited the Nether.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<Box<Distance>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player places a block.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:placed_block",
+    ///   "conditions": {
+    ///     "block": "minecraft:respawn_anchor"
+    ///   }
+    /// }"#, Criterion::PlacedBlock {
+    ///     block: Some("minecraft:respawn_anchor".parse().unwrap()),
+    ///     item: None,
+    ///     location: None,
+    ///     state: Default::default(),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:placed_block")]
+    PlacedBlock {
+        /// The block that was placed. Accepts block IDs.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<ResourceLocation>,
+        /// The item that was used to place the block before the item was consumed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The location of the block that was placed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<Box<Location>>,
+        /// Block states to match.
+        ///
+        /// - Key: A block state and its value.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        state: BTreeMap<String, PlainValue>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player generates the contents of a container with a loot table set.
+    #[serde(rename = "minecraft:player_generates_container_loot")]
+    PlayerGeneratesContainerLoot {
+        /// The resource location of the generated loot table.
+        loot_table: ResourceLocation,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player hurts a mob or player.
+    #[serde(rename = "minecraft:player_hurt_entity")]
+    PlayerHurtEntity {
+        /// The damage that was dealt.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        damage: Option<Box<Damage>>,
+        /// The entity that was damaged. May also be a list of predicates that must pass in order
+        /// for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player interacts with an entity.
+    #[serde(rename = "minecraft:player_interacted_with_entity")]
+    PlayerInteractedWithEntity {
+        /// The item which was in the player's hand during interaction.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The entity which was interacted with. May also be a list of predicates that must pass
+        /// in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after a player is the source of a mob or player being killed.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::conditions::{DamageSource, Entity};
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:player_killed_entity",
+    ///   "conditions": {
+    ///     "entity": {
+    ///       "type": "minecraft:skeleton"
+    ///     },
+    ///     "killing_blow": {
+    ///       "is_projectile": true
+    ///     }
+    ///   }
+    /// }"#, Criterion::PlayerKilledEntity {
+    ///     entity: Some(Either::Right(Box::new(Entity {
+    ///         r#type: Some("minecraft:skeleton".parse().unwrap()),
+    ///         ..Entity::default()
+    ///     }))),
+    ///     killing_blow: Some(Box::new(DamageSource {
+    ///         is_projectile: Some(true),
+    ///         ..DamageSource::default()
+    ///     })),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:player_killed_entity")]
+    PlayerKilledEntity {
+        /// The entity that was killed. May also be a list of predicates that must pass in order
+        /// for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The type of damage that killed an entity.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        killing_blow: Option<Box<DamageSource>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player unlocks a recipe (using a knowledge book for example).
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:recipe_unlocked",
+    ///   "conditions": {
+    ///     "recipe": "minecraft:oak_planks"
+    ///   }
+    /// }"#, Criterion::RecipeUnlocked {
+    ///     recipe: "minecraft:oak_planks".parse().unwrap(),
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:recipe_unlocked")]
+    RecipeUnlocked {
+        /// The recipe that was unlocked.
+        recipe: ResourceLocation,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player shoots a crossbow.
+    #[serde(rename = "minecraft:shot_crossbow")]
+    ShotCrossbow {
+        /// The crossbow that is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Triggers when the player enters a bed.
     ///
     /// ```
     /// # use minecraft_json::assert_equiv_pretty_protected;
     /// # use minecraft_json::minecraft::data::conditions::{Item, Location};
-    /// # use minecraft_json::minecraft::data::advancement::Criterion;
-    /// # use minecraft_json::minecraft::common::{Vector3d, Ranged};
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Vector3d, Ranged, Extra};
     /// assert_equiv_pretty_protected!(r#"{
     ///   "trigger": "minecraft:slept_in_bed",
     ///   "conditions": {
@@ -420,7 +1297,7 @@ pub enum Criterion {
     ///   }
     /// }"#, Criterion::SleptInBed {
     ///     location: Some(Box::new(Location {
-    ///         biome: Some("minecraft:desert".to_string()),
+    ///         biome: Some("minecraft:desert".parse().unwrap()),
     ///         feature: Some("village".to_string()),
     ///         position: Some(Box::new(Vector3d {
     ///             y: Some(Ranged::Range {
@@ -432,6 +1309,8 @@ pub enum Criterion {
     ///         ..Location::default()
     ///     })),
     ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
     /// });
     /// ```
     #[serde(rename = "minecraft:slept_in_bed")]
@@ -443,27 +1322,649 @@ pub enum Criterion {
         /// must pass in order for the trigger to activate.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player slides down a block.
+    #[serde(rename = "minecraft:slide_down_block")]
+    SlideDownBlock {
+        /// The block that the player slid on. Accepts block IDs.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<ResourceLocation>,
+        /// Block states to match.
+        ///
+        /// - Key: A block state and its value.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        state: BTreeMap<String, PlainValue>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player starts riding a vehicle or an entity starts riding a vehicle
+    /// currently ridden by the player.
+    #[serde(rename = "minecraft:started_riding")]
+    StartedRiding {
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after an entity has been summoned. Works with iron golems, snow golems, the
+    /// ender dragon and the wither.
+    #[serde(rename = "minecraft:summoned_entity")]
+    SummonedEntity {
+        /// The summoned entity. May also be a list of predicates that must pass in order for the
+        /// trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player tames an animal.
+    #[serde(rename = "minecraft:tame_animal")]
+    TameAnimal {
+        /// Checks the entity that was tamed. May also be a list of predicates that must pass in
+        /// order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
+    /// Triggers when the player shoots a target block.
+    #[serde(rename = "minecraft:target_hit")]
+    TargetHit {
+        /// The redstone signal that will come out of the target block.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signal_strength: Option<Ranged<isize>>,
+        /// The projectile used to hit the target block. May also be a list of predicates that
+        /// must pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        projectile: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player throws an item and another entity picks it up.
+    #[serde(rename = "minecraft:thrown_item_picked_up_by_entity")]
+    ThrownItemPickedUpByEntity {
+        /// The thrown item which was picked up.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The entity which picked up the item. May also be a list of predicates that must pass
+        /// in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers every tick (20 times a second).
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// let criterion: Criterion = serde_json::from_str(r#"{"trigger": "minecraft:tick"}"#).unwrap();
+    /// assert_eq!(criterion, Criterion::Tick { player: None, spelling: TriggerSpelling::Namespaced, extra: Extra::default() });
+    /// ```
+    #[serde(rename = "minecraft:tick")]
+    Tick {
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player uses an eye of ender (in a world where strongholds generate).
+    #[serde(rename = "minecraft:used_ender_eye")]
+    UsedEnderEye {
+        /// The horizontal distance between the player and the stronghold.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<Ranged<Number>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player uses a totem.
+    #[serde(rename = "minecraft:used_totem")]
+    UsedTotem {
+        /// The item, only works with totem items.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers for every tick that the player uses an item that is used continuously.
+    #[serde(rename = "minecraft:using_item")]
+    UsingItem {
+        /// The item that is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers after the player trades with a villager or a wandering trader.
+    ///
+    /// ```
+    /// # use minecraft_json::assert_equiv_pretty;
+    /// # use minecraft_json::minecraft::data::conditions::Item;
+    /// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
+    /// assert_equiv_pretty!(r#"{
+    ///   "trigger": "minecraft:villager_trade",
+    ///   "conditions": {
+    ///     "item": {
+    ///       "items": [
+    ///         "minecraft:emerald"
+    ///       ]
+    ///     }
+    ///   }
+    /// }"#, Criterion::VillagerTrade {
+    ///     item: Some(Box::new(Item {
+    ///         items: Some(Either::Right(vec!["minecraft:emerald".parse().unwrap()])),
+    ///         ..Item::default()
+    ///     })),
+    ///     villager: None,
+    ///     player: None,
+    ///     spelling: TriggerSpelling::Namespaced,
+    ///     extra: Extra::default(),
+    /// });
+    /// ```
+    #[serde(rename = "minecraft:villager_trade")]
+    VillagerTrade {
+        /// The item that was purchased. The "count" tag checks the count from one trade, not
+        /// multiple.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        item: Option<Box<Item>>,
+        /// The villager the item was purchased from. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        villager: Option<PredicatesOrEntity>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Triggers when the player causes a raid.
+    #[serde(rename = "minecraft:voluntary_exile")]
+    VoluntaryExile {
+        /// The location of the player.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<Box<Location>>,
+        /// The player that would get the advancement. May also be a list of predicates that must
+        /// pass in order for the trigger to activate.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player: Option<PredicatesOrEntity>,
+        /// How the trigger is written, see [`TriggerSpelling`].
+        #[serde(skip)]
+        spelling: TriggerSpelling,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// A custom trigger registered with [`register_trigger`](extension::register_trigger).
+    #[serde(skip)]
+    Custom {
+        /// The trigger ID, as written.
+        trigger: String,
+        /// The typed conditions.
+        conditions: Box<dyn CustomCondition>,
+    },
+    /// A trigger unknown to this crate, typically from a mod or plugin. The conditions are kept
+    /// verbatim, so that the criterion can be written back unchanged.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::advancement::Criterion;
+    /// let json = r#"{"trigger":"create:bracket_apply","conditions":{"accepted_entries":["create:cogwheel"]}}"#;
+    /// let criterion: Criterion = serde_json::from_str(json).unwrap();
+    /// match &criterion {
+    ///     Criterion::Other { trigger, conditions } => {
+    ///         assert_eq!(trigger, "create:bracket_apply");
+    ///         assert_eq!(conditions.as_ref().unwrap().get(), r#"{"accepted_entries":["create:cogwheel"]}"#);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// assert_eq!(serde_json::to_string(&criterion).unwrap(), json);
+    /// ```
+    #[serde(skip)]
+    Other {
+        /// The trigger ID, as written.
+        trigger: String,
+        /// The raw `conditions` object, if present.
+        conditions: Option<RawJson>,
+    },
+}
+
+impl Serialize for Criterion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Criterion::Other { trigger, conditions } => {
+                let mut state = serializer.serialize_struct("Criterion", 2)?;
+                state.serialize_field("trigger", trigger)?;
+                match conditions {
+                    Some(conditions) => state.serialize_field("conditions", conditions)?,
+                    None => state.skip_field("conditions")?,
+                }
+                state.end()
+            }
+            Criterion::Custom { trigger, conditions } => {
+                let mut state = serializer.serialize_struct("Criterion", 2)?;
+                state.serialize_field("trigger", trigger)?;
+                state.serialize_field("conditions", &conditions.to_value().map_err(S::Error::custom)?)?;
+                state.end()
+            }
+            _ if matches!(self.spelling(), Some(TriggerSpelling::Short)) => {
+                let mut criterion = Criterion::serialize(self, serde_json::value::Serializer).map_err(S::Error::custom)?;
+                if let Some(Value::String(trigger)) = criterion.get_mut("trigger") {
+                    *trigger = ResourceLocation::normalise(trigger).split_off("minecraft:".len());
+                }
+                criterion.serialize(serializer)
+            }
+            _ => Criterion::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Criterion {
+    /// Same as the derived implementation, except that:
+    ///
+    /// - the `minecraft` namespace may be omitted from vanilla triggers, see [`TriggerSpelling`];
+    /// - `conditions` may be omitted (as is common for `impossible` and `tick`), in which case it
+    ///   is taken to be empty;
+    /// - triggers registered in [`extension`] are parsed as [`Criterion::Custom`];
+    /// - unknown triggers are kept verbatim as [`Criterion::Other`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut criterion = Map::deserialize(deserializer)?;
+        let trigger = match criterion.get("trigger") {
+            Some(Value::String(trigger)) => trigger.clone(),
+            _ => return error::nested_value(Value::Object(criterion), |d| Criterion::deserialize(d)),
+        };
+        let id = ResourceLocation::normalise(&trigger);
+        if let Some(parse) = extension::trigger(&id) {
+            let conditions = criterion.shift_remove("conditions").unwrap_or_else(|| Value::Object(Map::new()));
+            let conditions = parse(conditions).map_err(D::Error::custom)?;
+            return Ok(Criterion::Custom { trigger, conditions });
+        }
+        if !Criterion::VANILLA_TRIGGERS.contains(&id.as_str()) {
+            let conditions = criterion.shift_remove("conditions")
+                .map(|conditions| serde_json::value::to_raw_value(&conditions).map(RawJson))
+                .transpose().map_err(D::Error::custom)?;
+            return Ok(Criterion::Other { trigger, conditions });
+        }
+        let short = id != trigger;
+        criterion.insert("trigger".to_string(), Value::String(id));
+        criterion.entry("conditions").or_insert_with(|| Value::Object(Map::new()));
+        let mut criterion = error::nested_value(Value::Object(criterion), |d| Criterion::deserialize(d))?;
+        if let (true, Some(spelling)) = (short, criterion.spelling_mut()) {
+            *spelling = TriggerSpelling::Short;
+        }
+        Ok(criterion)
+    }
+}
+
+/// How the trigger of a vanilla [`Criterion`] is written: the `minecraft` namespace may be
+/// omitted. Kept so that criteria are written back as they were read, but ignored when comparing
+/// criteria, as for [`ResourceLocation`].
+///
+/// ```
+/// # use minecraft_json::minecraft::data::advancement::{Criterion, TriggerSpelling};
+/// # use minecraft_json::minecraft::common::Extra;
+/// let json = r#"{"trigger":"slept_in_bed"}"#;
+/// let criterion: Criterion = serde_json::from_str(json).unwrap();
+/// assert!(matches!(&criterion, Criterion::SleptInBed { spelling: TriggerSpelling::Short, .. }));
+/// assert_eq!(serde_json::to_string(&criterion).unwrap(), r#"{"trigger":"slept_in_bed","conditions":{}}"#);
+/// assert_eq!(criterion, Criterion::SleptInBed {
+///     location: None,
+///     player: None,
+///     spelling: TriggerSpelling::Namespaced,
+///     extra: Extra::default(),
+/// });
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[derive(Derivative)]
+#[derivative(Default)]
+pub enum TriggerSpelling {
+    /// With the namespace, e.g. `minecraft:tick`.
+    #[derivative(Default)]
+    Namespaced,
+    /// Without the namespace, e.g. `tick`.
+    Short,
+}
+
+impl PartialEq for TriggerSpelling {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Eq for TriggerSpelling {}
+
+/// Schema of [`Criterion`], as it is actually deserialised: the derived schema requires
+/// `conditions` and the `minecraft` namespace in vanilla triggers, but both may be omitted.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::advancement::Advancement;
+/// let schema = serde_json::to_value(schemars::schema_for!(Advancement)).unwrap();
+/// let criterion = &schema["definitions"]["Criterion"]["oneOf"];
+/// let slept_in_bed = criterion.as_array().unwrap().iter()
+///     .find(|alt| alt["properties"]["trigger"]["enum"][0] == "minecraft:slept_in_bed")
+///     .unwrap();
+/// // a criterion `{"trigger": "slept_in_bed"}` matches the schema
+/// assert_eq!(slept_in_bed["required"], serde_json::json!(["trigger"]));
+/// let triggers = slept_in_bed["properties"]["trigger"]["enum"].as_array().unwrap();
+/// assert!(triggers.contains(&"slept_in_bed".into()));
+/// ```
+#[cfg(feature = "schema")]
+struct CriterionSchema;
+
+#[cfg(feature = "schema")]
+impl JsonSchema for CriterionSchema {
+    fn schema_name() -> String { Criterion::schema_name() }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::Schema;
+        let mut schema = Criterion::json_schema(gen);
+        let alternatives = match &mut schema {
+            Schema::Object(obj) => obj.subschemas().one_of.iter_mut().flatten(),
+            Schema::Bool(_) => return schema,
+        };
+        for alternative in alternatives {
+            let object = match alternative {
+                Schema::Object(obj) => obj.object(),
+                Schema::Bool(_) => continue,
+            };
+            object.required.remove("conditions");
+            if let Some(Schema::Object(trigger)) = object.properties.get_mut("trigger") {
+                if let Some(values) = &mut trigger.enum_values {
+                    let short: Vec<Value> = values.iter()
+                        .filter_map(|v| Some(Value::from(v.as_str()?.strip_prefix("minecraft:")?)))
+                        .collect();
+                    values.extend(short);
+                }
+            }
+        }
+        schema
+    }
+}
+
+/// Match the spelling of the trigger of a vanilla criterion, see [`TriggerSpelling`].
+macro_rules! spelling {
+    ($criterion: expr, $spelling: ident => $result: expr) => {
+        match $criterion {
+            Criterion::BeeNestDestroyed { spelling: $spelling, .. }
+            | Criterion::BredAnimals { spelling: $spelling, .. }
+            | Criterion::BrewedPotion { spelling: $spelling, .. }
+            | Criterion::ChangedDimension { spelling: $spelling, .. }
+            | Criterion::ChanneledLightning { spelling: $spelling, .. }
+            | Criterion::ConstructBeacon { spelling: $spelling, .. }
+            | Criterion::ConsumeItem { spelling: $spelling, .. }
+            | Criterion::CuredZombieVillager { spelling: $spelling, .. }
+            | Criterion::EffectsChanged { spelling: $spelling, .. }
+            | Criterion::EnchantedItem { spelling: $spelling, .. }
+            | Criterion::EnterBlock { spelling: $spelling, .. }
+            | Criterion::EntityHurtPlayer { spelling: $spelling, .. }
+            | Criterion::EntityKilledPlayer { spelling: $spelling, .. }
+            | Criterion::FilledBucket { spelling: $spelling, .. }
+            | Criterion::FishingRodHooked { spelling: $spelling, .. }
+            | Criterion::HeroOfTheVillage { spelling: $spelling, .. }
+            | Criterion::HoneyBlockSlide { spelling: $spelling, .. }
+            | Criterion::Impossible { spelling: $spelling, .. }
+            | Criterion::InventoryChanged { spelling: $spelling, .. }
+            | Criterion::ItemDurabilityChanged { spelling: $spelling, .. }
+            | Criterion::ItemUsedOnBlock { spelling: $spelling, .. }
+            | Criterion::KilledByCrossbow { spelling: $spelling, .. }
+            | Criterion::Levitation { spelling: $spelling, .. }
+            | Criterion::LightningStrike { spelling: $spelling, .. }
+            | Criterion::Location { spelling: $spelling, .. }
+            | Criterion::NetherTravel { spelling: $spelling, .. }
+            | Criterion::PlacedBlock { spelling: $spelling, .. }
+            | Criterion::PlayerGeneratesContainerLoot { spelling: $spelling, .. }
+            | Criterion::PlayerHurtEntity { spelling: $spelling, .. }
+            | Criterion::PlayerInteractedWithEntity { spelling: $spelling, .. }
+            | Criterion::PlayerKilledEntity { spelling: $spelling, .. }
+            | Criterion::RecipeUnlocked { spelling: $spelling, .. }
+            | Criterion::ShotCrossbow { spelling: $spelling, .. }
+            | Criterion::SleptInBed { spelling: $spelling, .. }
+            | Criterion::SlideDownBlock { spelling: $spelling, .. }
+            | Criterion::StartedRiding { spelling: $spelling, .. }
+            | Criterion::SummonedEntity { spelling: $spelling, .. }
+            | Criterion::TameAnimal { spelling: $spelling, .. }
+            | Criterion::TargetHit { spelling: $spelling, .. }
+            | Criterion::ThrownItemPickedUpByEntity { spelling: $spelling, .. }
+            | Criterion::Tick { spelling: $spelling, .. }
+            | Criterion::UsedEnderEye { spelling: $spelling, .. }
+            | Criterion::UsedTotem { spelling: $spelling, .. }
+            | Criterion::UsingItem { spelling: $spelling, .. }
+            | Criterion::VillagerTrade { spelling: $spelling, .. }
+            | Criterion::VoluntaryExile { spelling: $spelling, .. } => Some($result),
+            Criterion::Custom { .. } | Criterion::Other { .. } => None,
+        }
+    };
+}
+
+impl Criterion {
+    /// How the trigger is written, unless this is a [`Criterion::Custom`] or [`Criterion::Other`].
+    pub fn spelling(&self) -> Option<TriggerSpelling> { spelling!(self, spelling => *spelling) }
+
+    /// Mutable access to [`Criterion::spelling`], e.g. to write the trigger without namespace.
+    pub fn spelling_mut(&mut self) -> Option<&mut TriggerSpelling> { spelling!(self, spelling => spelling) }
+
+    /// IDs of all vanilla triggers, i.e. those not parsed as [`Criterion::Custom`] or
+    /// [`Criterion::Other`].
+    pub const VANILLA_TRIGGERS: &'static [&'static str] = &[
+        "minecraft:bee_nest_destroyed", "minecraft:bred_animals", "minecraft:brewed_potion",
+        "minecraft:changed_dimension", "minecraft:channeled_lightning", "minecraft:construct_beacon",
+        "minecraft:consume_item", "minecraft:cured_zombie_villager", "minecraft:effects_changed",
+        "minecraft:enchanted_item", "minecraft:enter_block", "minecraft:entity_hurt_player",
+        "minecraft:entity_killed_player", "minecraft:filled_bucket", "minecraft:fishing_rod_hooked",
+        "minecraft:hero_of_the_village", "minecraft:honey_block_slide", "minecraft:impossible",
+        "minecraft:inventory_changed", "minecraft:item_durability_changed",
+        "minecraft:item_used_on_block", "minecraft:killed_by_crossbow", "minecraft:levitation",
+        "minecraft:lightning_strike", "minecraft:location", "minecraft:nether_travel",
+        "minecraft:placed_block", "minecraft:player_generates_container_loot",
+        "minecraft:player_hurt_entity", "minecraft:player_interacted_with_entity",
+        "minecraft:player_killed_entity", "minecraft:recipe_unlocked", "minecraft:shot_crossbow",
+        "minecraft:slept_in_bed", "minecraft:slide_down_block", "minecraft:started_riding",
+        "minecraft:summoned_entity", "minecraft:tame_animal", "minecraft:target_hit",
+        "minecraft:thrown_item_picked_up_by_entity", "minecraft:tick", "minecraft:used_ender_eye",
+        "minecraft:used_totem", "minecraft:using_item", "minecraft:villager_trade",
+        "minecraft:voluntary_exile",
+    ];
+
+    /// All fields of this criterion that are entity conditions, or lists of predicate IDs, by
+    /// their paths in `conditions`, e.g. `player`, `victims[0]` or `damage.source_entity`.
+    pub fn predicates_or_entities(&self) -> Vec<(String, &PredicatesOrEntity)> {
+        fn field<'a>(name: &str, value: &'a Option<PredicatesOrEntity>) -> (String, Option<&'a PredicatesOrEntity>) {
+            (name.to_string(), value.as_ref())
+        }
+        fn damage_source(damage: &Option<Box<Damage>>) -> (String, Option<&PredicatesOrEntity>) {
+            ("damage.source_entity".to_string(), damage.as_ref().and_then(|d| d.source_entity.as_ref()))
+        }
+        let fields = match self {
+            Criterion::BeeNestDestroyed { player, .. }
+            | Criterion::BrewedPotion { player, .. }
+            | Criterion::ChangedDimension { player, .. }
+            | Criterion::ConstructBeacon { player, .. }
+            | Criterion::ConsumeItem { player, .. }
+            | Criterion::EnchantedItem { player, .. }
+            | Criterion::EnterBlock { player, .. }
+            | Criterion::FilledBucket { player, .. }
+            | Criterion::HeroOfTheVillage { player, .. }
+            | Criterion::HoneyBlockSlide { player, .. }
+            | Criterion::InventoryChanged { player, .. }
+            | Criterion::ItemDurabilityChanged { player, .. }
+            | Criterion::ItemUsedOnBlock { player, .. }
+            | Criterion::Levitation { player, .. }
+            | Criterion::Location { player, .. }
+            | Criterion::NetherTravel { player, .. }
+            | Criterion::PlacedBlock { player, .. }
+            | Criterion::PlayerGeneratesContainerLoot { player, .. }
+            | Criterion::RecipeUnlocked { player, .. }
+            | Criterion::ShotCrossbow { player, .. }
+            | Criterion::SleptInBed { player, .. }
+            | Criterion::SlideDownBlock { player, .. }
+            | Criterion::StartedRiding { player, .. }
+            | Criterion::Tick { player, .. }
+            | Criterion::UsedEnderEye { player, .. }
+            | Criterion::UsedTotem { player, .. }
+            | Criterion::UsingItem { player, .. }
+            | Criterion::VoluntaryExile { player, .. } => vec![field("player", player)],
+            Criterion::EntityKilledPlayer { entity, player, .. }
+            | Criterion::FishingRodHooked { entity, player, .. }
+            | Criterion::PlayerInteractedWithEntity { entity, player, .. }
+            | Criterion::PlayerKilledEntity { entity, player, .. }
+            | Criterion::SummonedEntity { entity, player, .. }
+            | Criterion::TameAnimal { entity, player, .. }
+            | Criterion::ThrownItemPickedUpByEntity { entity, player, .. } =>
+                vec![field("entity", entity), field("player", player)],
+            Criterion::BredAnimals { child, parent, partner, player, .. } => vec![
+                field("child", child), field("parent", parent),
+                field("partner", partner), field("player", player),
+            ],
+            Criterion::CuredZombieVillager { villager, zombie, player, .. } =>
+                vec![field("villager", villager), field("zombie", zombie), field("player", player)],
+            Criterion::EffectsChanged { source, player, .. } =>
+                vec![field("source", source), field("player", player)],
+            Criterion::EntityHurtPlayer { damage, player, .. } =>
+                vec![damage_source(damage), field("player", player)],
+            Criterion::PlayerHurtEntity { damage, entity, player, .. } =>
+                vec![damage_source(damage), field("entity", entity), field("player", player)],
+            Criterion::ChanneledLightning { victims, player, .. }
+            | Criterion::KilledByCrossbow { victims, player, .. } => victims.iter().enumerate()
+                .map(|(k, victim)| (format!("victims[{}]", k), Some(victim)))
+                .chain(std::iter::once(field("player", player)))
+                .collect(),
+            Criterion::LightningStrike { lightning, bystander, player, .. } =>
+                vec![field("lightning", lightning), field("bystander", bystander), field("player", player)],
+            Criterion::TargetHit { projectile, player, .. } =>
+                vec![field("projectile", projectile), field("player", player)],
+            Criterion::VillagerTrade { villager, player, .. } =>
+                vec![field("villager", villager), field("player", player)],
+            Criterion::Impossible { .. } | Criterion::Custom { .. } | Criterion::Other { .. } => Vec::new(),
+        };
+        fields.into_iter().filter_map(|(name, field)| Some((name, field?))).collect()
+    }
+}
+
+/// Number of slots in the player's inventory, for [`Criterion::InventoryChanged`].
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct InventorySlots {
+    /// The amount of slots empty in the inventory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty: Option<Ranged<isize>>,
+    /// The amount of slots completely filled (stacksize) in the inventory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<Ranged<isize>>,
+    /// The amount of slots occupied in the inventory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occupied: Option<Ranged<isize>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// An object representing the rewards provided when an [`Advancement`] is obtained.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Derivative, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Rewards {
     /// A list of recipes to unlock.
     ///
     /// Item: A namespaced ID for a recipe.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub recipes: Vec<String>,
+    pub recipes: Vec<ResourceLocation>,
     /// A list of loot tables to give to the player.
     ///
     /// Item: A namespaced ID for a loot table.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub loot: Vec<String>,
+    pub loot: Vec<ResourceLocation>,
     /// An amount of experience.
     #[serde(default, skip_serializing_if = "defaults::is_default")]
     pub experience: isize,
     /// A function to run. Function tags are not allowed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub function: Option<String>,
+    pub function: Option<ResourceLocation>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
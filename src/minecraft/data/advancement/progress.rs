@@ -0,0 +1,160 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Evaluating [`requirements`](Advancement::requirements) of an [`Advancement`] against a set of
+//! completed criteria.
+//!
+//! Requirements are AND grouping of OR groups: an advancement is granted when every group has
+//! at least one completed criterion. Empty requirements mean every criterion is required.
+//!
+//! ```
+//! # use std::collections::BTreeSet;
+//! # use minecraft_json::minecraft::data::advancement::Advancement;
+//! # use minecraft_json::minecraft::data::advancement::progress::{Progress, RequirementIssue};
+//! let advancement: Advancement = serde_json::from_str(r#"{
+//!   "criteria": {
+//!     "a": {"trigger": "minecraft:slept_in_bed", "conditions": {}},
+//!     "b": {"trigger": "minecraft:slept_in_bed", "conditions": {}},
+//!     "c": {"trigger": "minecraft:slept_in_bed", "conditions": {}},
+//!     "d": {"trigger": "minecraft:slept_in_bed", "conditions": {}}
+//!   },
+//!   "requirements": [["a", "b"], ["c", "e"]]
+//! }"#).unwrap();
+//! let completed: BTreeSet<_> = vec!["b"].into_iter().collect();
+//! let progress = advancement.progress(&completed);
+//! assert_eq!(progress, Progress { completed: 1, total: 2 });
+//! assert!(!progress.is_done());
+//! assert_eq!(progress.fraction(), 0.5);
+//! assert_eq!(advancement.requirement_issues(), [
+//!     RequirementIssue::UnknownCriterion { group: 1, name: "e".to_string() },
+//!     RequirementIssue::UnusedCriterion("d".to_string()),
+//! ]);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use crate::minecraft::data::advancement::Advancement;
+
+/// Progress of an advancement, in terms of requirement groups.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Progress {
+    /// Number of requirement groups with at least one completed criterion.
+    pub completed: usize,
+    /// Total number of requirement groups.
+    pub total: usize,
+}
+
+impl Progress {
+    /// Whether or not the advancement is granted. An advancement without any requirements can
+    /// never be granted by criteria.
+    pub fn is_done(&self) -> bool {
+        self.total > 0 && self.completed == self.total
+    }
+
+    /// The fraction of completed requirement groups, between 0 and 1 (inclusive).
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.completed as f64 / self.total as f64 }
+    }
+}
+
+/// Problems in the [`requirements`](Advancement::requirements) of an [`Advancement`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum RequirementIssue {
+    /// A requirement group mentions a criterion not in [`criteria`](Advancement::criteria).
+    UnknownCriterion {
+        /// Index of the requirement group.
+        group: usize,
+        /// Name of the unknown criterion.
+        name: String,
+    },
+    /// A requirement group is empty, so it can never be satisfied.
+    EmptyGroup(usize),
+    /// A criterion is not mentioned in any requirement group, so it has no effect.
+    UnusedCriterion(String),
+}
+
+impl Display for RequirementIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            RequirementIssue::UnknownCriterion { group, name } =>
+                write!(f, "requirement group {} mentions unknown criterion '{}'", group, name),
+            RequirementIssue::EmptyGroup(group) =>
+                write!(f, "requirement group {} is empty and can never be satisfied", group),
+            RequirementIssue::UnusedCriterion(name) =>
+                write!(f, "criterion '{}' is not mentioned in any requirement group", name),
+        }
+    }
+}
+
+impl Advancement {
+    /// The effective requirement groups, applying the default of one group per criterion if
+    /// [`requirements`](Advancement::requirements) is empty.
+    pub fn requirement_groups(&self) -> Vec<Vec<&str>> {
+        if self.requirements.is_empty() {
+            self.criteria.keys().map(|name| vec![name.as_str()]).collect()
+        } else {
+            self.requirements.iter()
+                .map(|group| group.iter().map(String::as_str).collect())
+                .collect()
+        }
+    }
+
+    /// Compute the progress given a set of completed criterion names.
+    pub fn progress<S: Borrow<str> + Ord>(&self, completed: &BTreeSet<S>) -> Progress {
+        let groups = self.requirement_groups();
+        Progress {
+            completed: groups.iter()
+                .filter(|group| group.iter().any(|name| completed.contains(*name)))
+                .count(),
+            total: groups.len(),
+        }
+    }
+
+    /// Whether or not this advancement is granted given a set of completed criterion names.
+    pub fn is_granted<S: Borrow<str> + Ord>(&self, completed: &BTreeSet<S>) -> bool {
+        self.progress(completed).is_done()
+    }
+
+    /// Report unknown criterion names and empty groups in requirements, and criteria never
+    /// mentioned in any requirement group.
+    pub fn requirement_issues(&self) -> Vec<RequirementIssue> {
+        let mut issues = Vec::new();
+        let mut used = BTreeSet::new();
+        for (k, group) in self.requirements.iter().enumerate() {
+            if group.is_empty() {
+                issues.push(RequirementIssue::EmptyGroup(k));
+            }
+            for name in group {
+                if self.criteria.contains_key(name) {
+                    used.insert(name.as_str());
+                } else {
+                    issues.push(RequirementIssue::UnknownCriterion { group: k, name: name.clone() });
+                }
+            }
+        }
+        if !self.requirements.is_empty() {
+            for name in self.criteria.keys() {
+                if !used.contains(name.as_str()) {
+                    issues.push(RequirementIssue::UnusedCriterion(name.clone()));
+                }
+            }
+        }
+        issues
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Advancements form trees via their [`parent`](Advancement::parent) fields, one tree per tab
+//! in the advancement screen.
+//!
+//! ```
+//! # use std::collections::BTreeMap;
+//! # use minecraft_json::minecraft::data::advancement::Advancement;
+//! # use minecraft_json::minecraft::data::advancement::tree::{AdvancementTree, TreeIssue};
+//! let mut advancements = BTreeMap::new();
+//! for (id, parent) in [("demo:root", None), ("demo:a", Some("demo:root")),
+//!                      ("demo:b", Some("demo:a")), ("demo:c", Some("demo:d")),
+//!                      ("demo:d", Some("demo:c"))] {
+//!     advancements.insert(id.to_string(), Advancement {
+//!         parent: parent.map(|parent| parent.parse().unwrap()),
+//!         ..serde_json::from_str(r#"{"criteria": {}}"#).unwrap()
+//!     });
+//! }
+//! let tree = AdvancementTree::new(&advancements);
+//! assert_eq!(tree.roots(), ["demo:root"]);
+//! assert_eq!(tree.children("demo:root"), ["demo:a"]);
+//! assert_eq!(tree.depth("demo:b"), Some(2));
+//! assert_eq!(tree.ancestors("demo:b"), ["demo:a", "demo:root"]);
+//! assert_eq!(tree.depth("demo:c"), None);
+//! assert_eq!(tree.issues(), [TreeIssue::Cycle(vec!["demo:c".to_string(), "demo:d".to_string()])]);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use crate::minecraft::data::advancement::Advancement;
+
+/// Problems in the structure of an [`AdvancementTree`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum TreeIssue {
+    /// Advancements whose parents form a circle, which causes a loading failure. Listed from
+    /// the smallest ID, each followed by its parent; the last one has the first as its parent.
+    Cycle(Vec<String>),
+    /// The parent of an advancement does not exist.
+    MissingParent {
+        /// The advancement.
+        id: String,
+        /// Its missing parent.
+        parent: String,
+    },
+    /// An advancement sets [`Display::background`](crate::minecraft::data::advancement::Display::background)
+    /// but is not a root advancement, so the background has no effect.
+    BackgroundOnNonRoot {
+        /// The advancement.
+        id: String,
+    },
+    /// An advancement is the child of a [`hidden`](crate::minecraft::data::advancement::Display::hidden)
+    /// advancement, and is therefore hidden until its parent is completed.
+    ChildOfHidden {
+        /// The advancement.
+        id: String,
+        /// Its hidden parent.
+        parent: String,
+    },
+}
+
+impl Display for TreeIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TreeIssue::Cycle(ids) =>
+                write!(f, "circular parent references: {} -> {}", ids.join(" -> "), ids[0]),
+            TreeIssue::MissingParent { id, parent } =>
+                write!(f, "advancement '{}' has missing parent '{}'", id, parent),
+            TreeIssue::BackgroundOnNonRoot { id } =>
+                write!(f, "advancement '{}' sets a background but is not a root advancement", id),
+            TreeIssue::ChildOfHidden { id, parent } =>
+                write!(f, "advancement '{}' is hidden until its parent '{}' is completed", id, parent),
+        }
+    }
+}
+
+/// The tree structure of a set of advancements, keyed by namespaced IDs.
+#[derive(Debug)]
+pub struct AdvancementTree<'a> {
+    advancements: &'a BTreeMap<String, Advancement>,
+    parents: BTreeMap<&'a str, String>,
+    children: BTreeMap<&'a str, Vec<&'a str>>,
+    roots: Vec<&'a str>,
+    cyclic: BTreeSet<&'a str>,
+    issues: Vec<TreeIssue>,
+}
+
+impl<'a> AdvancementTree<'a> {
+    /// Build the tree for a set of advancements.
+    pub fn new(advancements: &'a BTreeMap<String, Advancement>) -> AdvancementTree<'a> {
+        let mut tree = AdvancementTree {
+            advancements,
+            parents: BTreeMap::new(),
+            children: BTreeMap::new(),
+            roots: Vec::new(),
+            cyclic: BTreeSet::new(),
+            issues: Vec::new(),
+        };
+        for (id, advancement) in advancements {
+            match &advancement.parent {
+                None => tree.roots.push(id),
+                Some(parent) => {
+                    let parent = parent.to_string();
+                    match advancements.get_key_value(&parent) {
+                        Some((parent, _)) => tree.children.entry(parent).or_default().push(id),
+                        None => tree.issues.push(TreeIssue::MissingParent {
+                            id: id.clone(),
+                            parent: parent.clone(),
+                        }),
+                    }
+                    tree.parents.insert(id, parent);
+                }
+            }
+        }
+        tree.find_cycles();
+        for (id, advancement) in advancements {
+            let display = match &advancement.display {
+                Some(display) => display,
+                None => continue,
+            };
+            if display.background.is_some() && advancement.parent.is_some() {
+                tree.issues.push(TreeIssue::BackgroundOnNonRoot { id: id.clone() });
+            }
+            if display.hidden {
+                for child in tree.children(id).to_vec() {
+                    tree.issues.push(TreeIssue::ChildOfHidden {
+                        id: child.to_string(),
+                        parent: id.clone(),
+                    });
+                }
+            }
+        }
+        tree
+    }
+
+    fn find_cycles(&mut self) {
+        let mut done = BTreeSet::new();
+        for start in self.advancements.keys() {
+            let mut path: Vec<&'a str> = Vec::new();
+            let mut current = Some(start.as_str());
+            while let Some(id) = current {
+                if done.contains(id) { break; }
+                if let Some(k) = path.iter().position(|p| *p == id) {
+                    let mut cycle = path[k..].to_vec();
+                    let min = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+                    cycle.rotate_left(min);
+                    self.cyclic.extend(cycle.iter().copied());
+                    self.issues.push(TreeIssue::Cycle(cycle.into_iter().map(str::to_string).collect()));
+                    break;
+                }
+                path.push(id);
+                current = self.parent(id);
+            }
+            done.extend(path);
+        }
+    }
+
+    /// The advancement with some ID, if it exists.
+    pub fn get(&self, id: &str) -> Option<&'a Advancement> {
+        self.advancements.get(id)
+    }
+
+    /// Root advancements, i.e. tabs in the advancement screen.
+    pub fn roots(&self) -> &[&'a str] {
+        &self.roots
+    }
+
+    /// The parent of an advancement, if it has one and the parent exists.
+    pub fn parent(&self, id: &str) -> Option<&'a str> {
+        let parent = self.parents.get(id)?;
+        self.advancements.get_key_value(parent).map(|(k, _)| k.as_str())
+    }
+
+    /// The direct children of an advancement.
+    pub fn children(&self, id: &str) -> &[&'a str] {
+        self.children.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether or not an advancement is part of a parent cycle.
+    pub fn is_cyclic(&self, id: &str) -> bool {
+        self.cyclic.contains(id)
+    }
+
+    /// All ancestors of an advancement, nearest first. Stops before repeating any advancement if
+    /// there is a cycle, and at a missing parent.
+    pub fn ancestors(&self, id: &str) -> Vec<&'a str> {
+        let mut result: Vec<&'a str> = Vec::new();
+        let mut current = self.parent(id);
+        while let Some(parent) = current {
+            if parent == id || result.contains(&parent) { break; }
+            result.push(parent);
+            current = self.parent(parent);
+        }
+        result
+    }
+
+    /// The depth of an advancement, i.e. 0 for roots, 1 for their children, and so on. `None` if
+    /// the advancement does not exist, or its ancestors do not lead to a root advancement.
+    pub fn depth(&self, id: &str) -> Option<usize> {
+        let advancement = self.advancements.get(id)?;
+        let ancestors = self.ancestors(id);
+        let top = ancestors.last().map_or(advancement, |top| &self.advancements[*top]);
+        if top.parent.is_some() { return None; }
+        Some(ancestors.len())
+    }
+
+    /// All problems found in the tree structure.
+    pub fn issues(&self) -> &[TreeIssue] {
+        &self.issues
+    }
+}
@@ -19,22 +19,29 @@
 //! Conditions common to all [`Criterion`](crate::minecraft::data::advancement::Criterion)s.
 
 use std::collections::BTreeMap;
-use serde::{Serialize, Deserialize};
-use crate::minecraft::common::{Ranged, Vector3d, PlainValue, Either};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::ser::SerializeMap;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::{Map, Number, Value};
+use crate::minecraft::common::{Ranged, Vector3d, PlainValue, Either, ResourceLocation, TagOrId, TagOrIds, Extra};
+use crate::minecraft::data::predicate::Predicate;
+use crate::minecraft::error;
 
 /// Tags common to all locations.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Location {
     /// The biome the entity is currently in.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub biome: Option<String>,
+    pub biome: Option<ResourceLocation>,
     /// The block at the location.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block: Option<Box<Block>>,
     /// The dimension the entity is currently in.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub dimension: Option<String>,
+    pub dimension: Option<ResourceLocation>,
     /// Name of a structure.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub feature: Option<String>,
@@ -50,44 +57,56 @@ pub struct Location {
     /// True if the block is closely above a campfire or soul campfire.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub smokey: Option<bool>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Block conditions.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Block {
     /// A list of block IDs.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub blocks: Vec<String>,
+    pub blocks: Vec<ResourceLocation>,
     /// The block Tag.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+    pub tag: Option<ResourceLocation>,
     /// The block NBT.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nbt: Option<String>,
     /// A map of block property names to values. Test will fail if the block doesn't match.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub state: BTreeMap<String, PlainValue>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Fluid conditions.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Fluid {
     /// The fluid ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub fluid: Option<String>,
+    pub fluid: Option<ResourceLocation>,
     /// The fluid Tag.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+    pub tag: Option<ResourceLocation>,
     /// A map of fluid property names to values. Test will fail if the fluid doesn't match.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub state: BTreeMap<String, PlainValue>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Tags common to all entities.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Entity {
     /// The player that would get the advancement. May also be a list of predicates that must
     /// pass in order for the trigger to activate.
@@ -97,14 +116,19 @@ pub struct Entity {
     ///
     /// Item: A status effect with the key name being the status effect name.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub effects: BTreeMap<String, Effect>,
+    pub effects: BTreeMap<ResourceLocation, Effect>,
     /// Equipments on this entity.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub equipment: Option<Equipment>,
+    /// Fishing hook properties to be checked. Fails when entity is not a fishing hook. Moved to
+    /// [`TypeSpecific::FishingHook`] since 1.19.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fishing_hook: Option<Box<FishingHook>>,
     /// Predicate Flags to be checked.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub flags: Option<EntityFlags>,
-    /// Lightning bolt properties to be checked. Fails when entity is not a lightning bolt.
+    /// Lightning bolt properties to be checked. Fails when entity is not a lightning bolt. Moved
+    /// to [`TypeSpecific::Lightning`] since 1.19.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lightning_bolt: Option<Box<LightningBolt>>,
     /// Location of this entity.
@@ -116,7 +140,8 @@ pub struct Entity {
     /// The entity directly riding this entity.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub passenger: Option<Box<Entity>>,
-    /// Player properties to be checked. Fails when entity is not a player.
+    /// Player properties to be checked. Fails when entity is not a player. Moved to
+    /// [`TypeSpecific::Player`] since 1.19.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub player: Option<Box<Player>>,
     /// Location predicate for the block the entity is standing on.
@@ -125,23 +150,33 @@ pub struct Entity {
     /// The team the entity belongs to.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub team: Option<String>,
-    /// An entity ID.
+    /// An entity ID, or an entity type tag prefixed with `#`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<TagOrId>,
+    /// Properties specific to one type of entity, since 1.19.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub r#type: Option<String>,
+    pub type_specific: Option<Box<TypeSpecific>>,
     /// The entity which this entity is targeting for attacks.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub targeted_entity: Option<Box<Entity>>,
     /// The vehicle that the entity is riding on.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub vehicle: Option<Box<Entity>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
+/// A predicate, either referenced by its ID or given inline.
+pub type PredicateOrId = Either<ResourceLocation, Predicate>;
+
 /// Either a list of predicates, or an entity.
-pub type PredicatesOrEntity = Either<Vec<String>, Box<Entity>>;
+pub type PredicatesOrEntity = Either<Vec<PredicateOrId>, Box<Entity>>;
 
 /// World distance.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[allow(missing_docs)]
 pub struct Distance {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -150,11 +185,15 @@ pub struct Distance {
     pub horizontal: Option<Ranged<isize>>,
     #[serde(flatten)]
     pub components: Vector3d<Ranged<isize>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Effect properties.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Effect {
     /// Whether the effect is from a beacon.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -168,11 +207,15 @@ pub struct Effect {
     /// Whether the effect has visible particles.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub visible: Option<bool>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Entity equipments.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[allow(missing_docs)]
 pub struct Equipment {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -187,11 +230,15 @@ pub struct Equipment {
     pub legs: Option<Box<Item>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub feet: Option<Box<Item>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Tags common to all items.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Item {
     /// Amount of the item.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -205,35 +252,147 @@ pub struct Item {
     /// List of stored enchantments.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stored_enchantments: Vec<Enchantment>,
-    /// A list of item IDs.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub items: Vec<String>,
+    /// A list of item IDs, or since Minecraft 1.20.5 also a single item ID or an item tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<TagOrIds>,
     /// An NBT string.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nbt: Option<String>,
     /// A brewed potion ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub potion: Option<String>,
+    pub potion: Option<ResourceLocation>,
     /// An item data pack tag.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+    pub tag: Option<ResourceLocation>,
+    /// Exact item components to match, since Minecraft 1.20.5.
+    ///
+    /// - Key: The component ID.
+    /// - Value: The exact component value.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, Value>,
+    /// Item sub-predicates to match, since Minecraft 1.20.5.
+    #[serde(default, skip_serializing_if = "ItemPredicates::is_empty")]
+    pub predicates: ItemPredicates,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Item sub-predicates, keyed by their IDs, since Minecraft 1.20.5. The `minecraft` namespace
+/// may be omitted from the IDs.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::conditions::Item;
+/// # use minecraft_json::minecraft::common::{Either, TagOrId};
+/// let json = r#"{
+///   "predicates": {
+///     "minecraft:damage": {"durability": {"min": 10}},
+///     "minecraft:enchantments": [{"enchantments": "minecraft:sharpness", "levels": 5}],
+///     "minecraft:custom_data": "{Unbreakable: 1b}",
+///     "minecraft:potion_contents": "minecraft:healing",
+///     "minecraft:trim": {"material": "minecraft:gold"}
+///   }
+/// }"#;
+/// let item: Item = serde_json::from_str(json).unwrap();
+/// let predicates = &item.predicates;
+/// assert!(predicates.damage.as_ref().unwrap().durability.is_some());
+/// assert!(matches!(&predicates.enchantments[0].enchantments,
+///                   Some(Either::Left(TagOrId::Id(id))) if id.path() == "sharpness"));
+/// assert_eq!(predicates.custom_data, Some(Either::Left("{Unbreakable: 1b}".to_string())));
+/// assert!(matches!(&predicates.potion_contents, Some(Either::Left(TagOrId::Id(id))) if id.path() == "healing"));
+/// assert_eq!(predicates.others["minecraft:trim"]["material"], "minecraft:gold");
+/// ```
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct ItemPredicates {
+    /// The durability and damage of the item.
+    #[serde(rename = "minecraft:damage", alias = "damage")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub damage: Option<DamagePredicate>,
+    /// Enchantments on the item. All entries must match.
+    #[serde(rename = "minecraft:enchantments", alias = "enchantments")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enchantments: Vec<EnchantmentPredicate>,
+    /// Enchantments stored in the item, e.g. in enchanted books. All entries must match.
+    #[serde(rename = "minecraft:stored_enchantments", alias = "stored_enchantments")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stored_enchantments: Vec<EnchantmentPredicate>,
+    /// Custom data of the item, as an SNBT string or a JSON object. The item data must contain
+    /// all the given tags.
+    #[serde(rename = "minecraft:custom_data", alias = "custom_data")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_data: Option<Either<String, Map<String, Value>>>,
+    /// The potion of the item, e.g. of potions or tipped arrows.
+    #[serde(rename = "minecraft:potion_contents", alias = "potion_contents")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub potion_contents: Option<TagOrIds>,
+    /// Sub-predicates not typed by this crate.
+    ///
+    /// - Key: The item sub-predicate ID, e.g. `minecraft:trim`.
+    /// - Value: The sub-predicate, whose structure depends on the ID.
+    #[serde(flatten)]
+    pub others: BTreeMap<String, Value>,
+}
+
+impl ItemPredicates {
+    /// Are there no sub-predicates?
+    pub fn is_empty(&self) -> bool { self == &ItemPredicates::default() }
+}
+
+/// The `minecraft:damage` item sub-predicate.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct DamagePredicate {
+    /// The remaining durability of the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub durability: Option<Ranged<isize>>,
+    /// The damage taken by the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub damage: Option<Ranged<isize>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// An entry of the `minecraft:enchantments` and `minecraft:stored_enchantments` item
+/// sub-predicates.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct EnchantmentPredicate {
+    /// The enchantments to match: an ID, a tag, or a list of IDs. Any enchantment if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enchantments: Option<TagOrIds>,
+    /// The level of the enchantment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub levels: Option<Ranged<isize>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Minecraft enchantment.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Enchantment {
     /// An enchantment ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub enchantment: Option<String>,
+    pub enchantment: Option<ResourceLocation>,
     /// The level of the enchantment.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub levels: Option<Ranged<isize>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Predicate Flags to be checked for an entity.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct EntityFlags {
     /// Test whether the entity is or is not on fire.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -250,11 +409,31 @@ pub struct EntityFlags {
     /// Test whether the entity is or is not a baby variant.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub is_baby: Option<bool>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Lightning bolt properties.
+///
+/// Fields not known to this crate are kept in the `extra` of [`lightning_entity`], see [`Extra`]:
+///
+/// ```
+/// # use minecraft_json::minecraft::data::conditions::LightningBolt;
+/// # use minecraft_json::minecraft::error;
+/// let json = r#"{"blocks_set_on_fire":1,"team":"red","bogus":1}"#;
+/// let lightning: LightningBolt = serde_json::from_str(json).unwrap();
+/// assert_eq!(lightning.lightning_entity.extra.0["bogus"], 1);
+/// assert_eq!(serde_json::to_string(&lightning).unwrap(), json);
+///
+/// let err = error::strict(|| error::from_str::<LightningBolt>(json)).unwrap_err();
+/// assert_eq!(err.to_string(), "bogus: unknown field at line 1 column 46");
+/// ```
+///
+/// [`lightning_entity`]: LightningBolt::lightning_entity
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct LightningBolt {
     /// Number of blocks set on fire by this lightning bolt.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -263,14 +442,100 @@ pub struct LightningBolt {
     /// match one or more entities.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub entity_struck: Option<Box<Entity>>,
-    /// Properties of this lightning bolt as an entity.
+    /// Properties of this lightning bolt as an entity, including fields not known to this crate.
     #[serde(flatten)]
     pub lightning_entity: Entity,
 }
 
+/// Fishing hook properties.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct FishingHook {
+    /// Whether the fishing hook was in open water.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_open_water: Option<bool>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Properties specific to one type of entity, in [`Entity::type_specific`].
+///
+/// ```
+/// # use minecraft_json::minecraft::data::conditions::{Entity, TypeSpecific};
+/// let json = r#"{"type_specific":{"type":"player","looking_at":{"team":"red"}}}"#;
+/// let entity: Entity = serde_json::from_str(json).unwrap();
+/// assert!(matches!(entity.type_specific.as_deref(), Some(TypeSpecific::Player(_))));
+/// assert_eq!(serde_json::to_string(&entity).unwrap(), json);
+///
+/// let json = r#"{"type":"minecraft:frog","variant":"minecraft:cold"}"#;
+/// let specific: TypeSpecific = serde_json::from_str(json).unwrap();
+/// assert!(matches!(&specific, TypeSpecific::Other { r#type, .. } if r#type == "minecraft:frog"));
+/// assert_eq!(serde_json::to_string(&specific).unwrap(), json);
+/// ```
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case", remote = "Self")]
+pub enum TypeSpecific {
+    /// Player properties.
+    #[serde(alias = "minecraft:player")]
+    Player(Player),
+    /// Fishing hook properties.
+    #[serde(alias = "minecraft:fishing_hook")]
+    FishingHook(FishingHook),
+    /// Lightning bolt properties.
+    #[serde(alias = "minecraft:lightning")]
+    Lightning(Box<LightningBolt>),
+    /// A type not modelled by this crate, e.g. `cat` or `frog`. All fields are kept in their
+    /// original order.
+    #[serde(skip)]
+    Other {
+        /// The type, as written.
+        r#type: String,
+        /// All fields except `type`.
+        properties: Map<String, Value>,
+    },
+}
+
+impl Serialize for TypeSpecific {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TypeSpecific::Other { r#type, properties } => {
+                let mut map = serializer.serialize_map(Some(properties.len() + 1))?;
+                map.serialize_entry("type", r#type)?;
+                for (key, value) in properties {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            _ => TypeSpecific::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeSpecific {
+    /// Same as the derived implementation, except that unknown types are parsed as
+    /// [`TypeSpecific::Other`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut properties = Map::deserialize(deserializer)?;
+        if let Some(Value::String(r#type)) = properties.get("type") {
+            let known = ["player", "fishing_hook", "lightning"];
+            if !known.contains(&r#type.strip_prefix("minecraft:").unwrap_or(r#type)) {
+                let r#type = r#type.clone();
+                properties.shift_remove("type");
+                return Ok(TypeSpecific::Other { r#type, properties });
+            }
+        }
+        error::nested_value(Value::Object(properties), |d| TypeSpecific::deserialize(d))
+    }
+}
+
 /// Player properties.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Player {
     /// The entity that the player is looking at, as long as it is visible and within a radius
     /// of 100 blocks. Visibility is defined through the line from the player's eyes to the
@@ -281,7 +546,7 @@ pub struct Player {
     ///
     /// Item: An advancement ID.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub advancements: BTreeMap<String, AdvancementStatus>,
+    pub advancements: BTreeMap<ResourceLocation, AdvancementStatus>,
     /// The game mode of the player.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub gamemode: Option<GameMode>,
@@ -292,15 +557,19 @@ pub struct Player {
     ///
     /// Item: A recipe ID. `True` if the recipe is known to the player.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub recipes: BTreeMap<String, bool>,
+    pub recipes: BTreeMap<ResourceLocation, bool>,
     /// List of [`Statistic`]s to match.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stats: Vec<Statistic>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Status of an advancement.
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 pub enum AdvancementStatus {
     /// Acquired?
@@ -309,9 +578,19 @@ pub enum AdvancementStatus {
     Criteria(BTreeMap<String, bool>),
 }
 
+impl<'de> Deserialize<'de> for AdvancementStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        error::untagged(deserializer, "AdvancementStatus", &[
+            &|v| error::variant(v, AdvancementStatus::Advancement),
+            &|v| error::variant(v, AdvancementStatus::Criteria),
+        ])
+    }
+}
+
 /// The game mode of a player.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[allow(missing_docs)]
 pub enum GameMode {
     Survival,
@@ -325,20 +604,25 @@ pub enum GameMode {
 /// use proper namespaces instead of the dot-notation (`minecraft.custom:minecraft.sneak_time`).
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Statistic {
     /// The statistic base. Possible values are `minecraft:custom`, `minecraft:crafted`,
     /// `minecraft:used`, `minecraft:broken`, `minecraft:mined`, `minecraft:killed`,
     /// `minecraft:picked_up`, `minecraft:dropped` and `minecraft:killed_by`.
-    pub r#type: String,
+    pub r#type: ResourceLocation,
     /// The statistic ID. Mostly mimics the criteria used for defining scoreboard objectives.
-    pub stat: String,
+    pub stat: ResourceLocation,
     /// The value of the statistic.
     pub value: Ranged<isize>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Properties of damage source.
 #[derive(Eq, PartialEq, Debug, Default)]
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct DamageSource {
     /// Checks if the damage bypassed the armor of the player (suffocation damage predominantly).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -370,4 +654,32 @@ pub struct DamageSource {
     /// Checks the entity that was the source of the damage (for example: The skeleton that shot the arrow).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_entity: Option<Box<Entity>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Properties of a damage instance, as in `entity_hurt_player` and `player_hurt_entity`.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Damage {
+    /// Checks if the damage was successfully blocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<bool>,
+    /// Checks the amount of incoming damage before damage reduction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dealt: Option<Ranged<Number>>,
+    /// Checks the entity that was the source of the damage (for example: The skeleton that shot the arrow).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_entity: Option<PredicatesOrEntity>,
+    /// Checks the amount of incoming damage after damage reduction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taken: Option<Ranged<Number>>,
+    /// Checks the type of damage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<Box<DamageSource>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -0,0 +1,159 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Registration of custom advancement triggers and predicate conditions, e.g. those added by
+//! mods. Registered IDs are parsed into [`Criterion::Custom`] and [`Predicate::Custom`], holding
+//! typed values which can be recovered with [`downcast_ref`](CustomCondition::downcast_ref).
+//!
+//! The registry is global, because serde offers no way to pass context into deserialisation.
+//! IDs in the `minecraft` namespace are reserved for vanilla and cannot be registered.
+//!
+//! ```
+//! # use serde::{Serialize, Deserialize};
+//! # use minecraft_json::minecraft::data::advancement::Criterion;
+//! # use minecraft_json::minecraft::data::predicate::Predicate;
+//! # use minecraft_json::minecraft::data::extension;
+//! #[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+//! struct BracketApply {
+//!     accepted_entries: Vec<String>,
+//! }
+//!
+//! #[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+//! struct InDimension {
+//!     dimension: String,
+//! }
+//!
+//! extension::register_trigger::<BracketApply>("create:bracket_apply");
+//! extension::register_condition::<InDimension>("mymod:in_dimension");
+//!
+//! let criterion: Criterion = serde_json::from_str(r#"{
+//!   "trigger": "create:bracket_apply",
+//!   "conditions": {"accepted_entries": ["create:cogwheel"]}
+//! }"#).unwrap();
+//! match &criterion {
+//!     Criterion::Custom { conditions, .. } => assert_eq!(
+//!         conditions.downcast_ref::<BracketApply>().unwrap().accepted_entries,
+//!         ["create:cogwheel"]),
+//!     _ => unreachable!(),
+//! }
+//!
+//! let json = r#"{"condition":"mymod:in_dimension","dimension":"minecraft:the_nether"}"#;
+//! let predicate: Predicate = serde_json::from_str(json).unwrap();
+//! assert_eq!(predicate, Predicate::Custom {
+//!     condition: "mymod:in_dimension".to_string(),
+//!     properties: Box::new(InDimension { dimension: "minecraft:the_nether".to_string() }),
+//! });
+//! assert_eq!(serde_json::to_string(&predicate).unwrap(), json);
+//! ```
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{PoisonError, RwLock};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::minecraft::common::ResourceLocation;
+
+#[cfg(doc)]
+use crate::minecraft::data::{advancement::Criterion, predicate::Predicate};
+
+/// Typed conditions of a custom trigger or predicate. Implemented for every type that can be
+/// serialised and compared for equality.
+pub trait CustomCondition: Debug + Send + Sync + 'static {
+    /// Serialise the conditions into a JSON value, which should be an object.
+    fn to_value(&self) -> serde_json::Result<Value>;
+    /// Compare with another custom condition, which is never equal if of a different type.
+    fn dyn_eq(&self, other: &dyn CustomCondition) -> bool;
+    /// Upcast to [`Any`] for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T> CustomCondition for T where T: Serialize + Eq + Debug + Send + Sync + 'static {
+    fn to_value(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+
+    fn dyn_eq(&self, other: &dyn CustomCondition) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+impl dyn CustomCondition {
+    /// The typed conditions, if they are of type `T`.
+    pub fn downcast_ref<T: CustomCondition>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+impl PartialEq for dyn CustomCondition {
+    fn eq(&self, other: &Self) -> bool { self.dyn_eq(other) }
+}
+
+impl Eq for dyn CustomCondition {}
+
+pub(crate) type Parser = fn(Value) -> serde_json::Result<Box<dyn CustomCondition>>;
+
+static TRIGGERS: RwLock<BTreeMap<String, Parser>> = RwLock::new(BTreeMap::new());
+static CONDITIONS: RwLock<BTreeMap<String, Parser>> = RwLock::new(BTreeMap::new());
+
+fn parse<T: CustomCondition + DeserializeOwned>(value: Value) -> serde_json::Result<Box<dyn CustomCondition>> {
+    Ok(Box::new(serde_json::from_value::<T>(value)?))
+}
+
+fn register<T: CustomCondition + DeserializeOwned>(registry: &RwLock<BTreeMap<String, Parser>>, id: &str) {
+    let id = ResourceLocation::normalise(id);
+    assert!(!id.starts_with("minecraft:"), "cannot register vanilla ID '{}'", id);
+    registry.write().unwrap_or_else(PoisonError::into_inner).insert(id, parse::<T>);
+}
+
+fn lookup(registry: &RwLock<BTreeMap<String, Parser>>, id: &str) -> Option<Parser> {
+    registry.read().unwrap_or_else(PoisonError::into_inner).get(&ResourceLocation::normalise(id)).copied()
+}
+
+/// Parse `conditions` of the advancement trigger `id` into `T`, replacing any previous
+/// registration for the same ID.
+///
+/// # Panics
+///
+/// If `id` is in the `minecraft` namespace.
+pub fn register_trigger<T: CustomCondition + DeserializeOwned>(id: &str) {
+    register::<T>(&TRIGGERS, id)
+}
+
+/// Parse predicates with `condition` set to `id` into `T`, replacing any previous registration
+/// for the same ID. `T` receives all fields except `condition`.
+///
+/// # Panics
+///
+/// If `id` is in the `minecraft` namespace.
+pub fn register_condition<T: CustomCondition + DeserializeOwned>(id: &str) {
+    register::<T>(&CONDITIONS, id)
+}
+
+/// The parser for `conditions` of a registered trigger.
+pub(crate) fn trigger(id: &str) -> Option<Parser> {
+    lookup(&TRIGGERS, id)
+}
+
+/// The parser for the fields of a registered predicate condition.
+pub(crate) fn condition(id: &str) -> Option<Parser> {
+    lookup(&CONDITIONS, id)
+}
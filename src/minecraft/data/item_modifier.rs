@@ -0,0 +1,642 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Item modifiers are technical JSON files that represent the functions to apply to items in
+//! loot tables, or to an item in the world via the `/item modify` command. They are a part of
+//! data packs.
+//!
+//! Inside a data pack, an item modifier is located within `data/<namespace>/item_modifiers`.
+//!
+//! An item modifier file may also contain an array of multiple function objects, in which case
+//! the functions are applied in order.
+//!
+//! ```
+//! # use minecraft_json::assert_equiv_pretty_protected;
+//! # use minecraft_json::minecraft::data::item_modifier::{ItemModifier, LootFunction};
+//! # use minecraft_json::minecraft::common::{Either, Extra};
+//! assert_equiv_pretty_protected!(r#"[
+//!   {
+//!     "function": "set_count",
+//!     "count": 3
+//!   },
+//!   {
+//!     "function": "explosion_decay"
+//!   }
+//! ]"#, ItemModifier::Right(vec![
+//!     LootFunction::SetCount {
+//!         count: Either::Left(3.into()),
+//!         add: false,
+//!         conditions: Vec::new(),
+//!         extra: Extra::default(),
+//!     },
+//!     LootFunction::ExplosionDecay {
+//!         conditions: Vec::new(),
+//!         extra: Extra::default(),
+//!     },
+//! ]));
+//! ```
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::Number;
+use crate::defaults;
+use crate::minecraft::common::{Either, NumberProviderValue, Ranged2, ResourceLocation, Extra};
+use crate::minecraft::text::TextComponent;
+use crate::minecraft::data::predicate::{Predicate, WhichEntity};
+use crate::minecraft::data::loot_table::Entry;
+
+/// An item modifier JSON file, either a single function or a list of functions.
+pub type ItemModifier = Either<LootFunction, Vec<LootFunction>>;
+
+/// Item functions (or loot functions).
+///
+/// ```
+/// # use minecraft_json::assert_equiv_pretty_protected;
+/// # use minecraft_json::minecraft::data::item_modifier::{LootFunction, BonusFormula};
+/// # use minecraft_json::minecraft::common::Extra;
+/// assert_equiv_pretty_protected!(r#"{
+///   "function": "apply_bonus",
+///   "enchantment": "minecraft:fortune",
+///   "formula": "minecraft:binomial_with_bonus_count",
+///   "parameters": {
+///     "extra": 3,
+///     "probability": 0.5714286
+///   }
+/// }"#, LootFunction::ApplyBonus {
+///     enchantment: "minecraft:fortune".parse().unwrap(),
+///     formula: BonusFormula::BinomialWithBonusCount {
+///         extra: 3,
+///         probability: "0.5714286".parse().unwrap(),
+///     },
+///     conditions: Vec::new(),
+///     extra: Extra::default(),
+/// });
+/// ```
+///
+/// Unknown fields are kept in `extra`, see [`Extra`]:
+///
+/// ```
+/// # use minecraft_json::minecraft::data::item_modifier::LootFunction;
+/// # use minecraft_json::minecraft::error;
+/// let json = r#"{"function":"set_count","count":2,"bogus":1}"#;
+/// let function: LootFunction = serde_json::from_str(json).unwrap();
+/// assert_eq!(serde_json::to_string(&function).unwrap(), json);
+///
+/// let err = error::strict(|| error::from_str::<LootFunction>(json)).unwrap_err();
+/// assert_eq!(err.to_string(), "bogus: unknown field at line 1 column 43");
+/// ```
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "function", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum LootFunction {
+    /// Applies a predefined bonus formula to the count of the item stack.
+    #[serde(alias = "minecraft:apply_bonus")]
+    ApplyBonus {
+        /// Enchantment ID used for level calculation.
+        enchantment: ResourceLocation,
+        /// The bonus formula, together with its parameters.
+        #[serde(flatten)]
+        formula: BonusFormula,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// For loot tables of type `block`, copies a block entity's `CustomName` tag into the item's
+    /// `display.Name` tag.
+    #[serde(alias = "minecraft:copy_name")]
+    CopyName {
+        /// The source to copy the name from.
+        source: NameSource,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Copies NBT values from a specified block entity or entity, or from command storage to the
+    /// item's `tag` tag.
+    #[serde(alias = "minecraft:copy_nbt")]
+    CopyNbt {
+        /// The source to copy NBT values from.
+        source: NbtSource,
+        /// A list of copy operations.
+        ops: Vec<CopyNbtOperation>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Copies state properties from dropped block to the item's `BlockStateTag` tag.
+    #[serde(alias = "minecraft:copy_state")]
+    CopyState {
+        /// A block ID. Function fails if block doesn't match.
+        block: ResourceLocation,
+        /// A list of property names to copy.
+        properties: Vec<String>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Enchants the item with one randomly-selected enchantment. The level of the enchantment,
+    /// if applicable, is random.
+    #[serde(alias = "minecraft:enchant_randomly")]
+    EnchantRandomly {
+        /// Optional. List of enchantment IDs to choose from. If omitted, all enchantments
+        /// applicable to the item are possible.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        enchantments: Vec<ResourceLocation>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Enchants the item with the specified enchantment level (roughly equivalent to using an
+    /// enchantment table at that level).
+    #[serde(alias = "minecraft:enchant_with_levels")]
+    EnchantWithLevels {
+        /// Determines whether treasure enchantments are allowed on this item.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        treasure: bool,
+        /// Number Provider. Specifies the exact enchantment level to use.
+        levels: NumberProviderValue<isize>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Converts an empty map into an explorer map leading to a nearby generated structure.
+    #[serde(alias = "minecraft:exploration_map")]
+    ExplorationMap {
+        /// The type of generated structure to locate. Accepts any of the `StructureType`s used
+        /// by the `/locate` command (case insensitive). Defaults to `buried_treasure`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        destination: Option<String>,
+        /// The icon used to mark the destination on the map. Accepts any of the map icon text
+        /// IDs (case insensitive). If `mansion` or `monument` is used, the color of the lines on
+        /// the item texture changes to match the corresponding explorer map. Defaults to
+        /// `mansion`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        decoration: Option<String>,
+        /// The zoom level of the resulting map. Defaults to 2.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        zoom: Option<isize>,
+        /// The size, in chunks, of the area to search for structures. The area checked is
+        /// square, not circular. Radius 0 causes only the current chunk to be searched, radius
+        /// 1 causes the current chunk and eight adjacent chunks to be searched, and so on.
+        /// Defaults to 50.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        search_radius: Option<isize>,
+        /// Don't search in chunks that have already been generated. Defaults to `true`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip_existing_chunks: Option<bool>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// For loot tables of type `block`, removes some items from a stack, if there was an
+    /// explosion. Each item has a chance of 1/explosion radius to be lost.
+    #[serde(alias = "minecraft:explosion_decay")]
+    ExplosionDecay {
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Smelts the item as it would be in a furnace without changing its count.
+    #[serde(alias = "minecraft:furnace_smelt")]
+    FurnaceSmelt {
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adds required item tags of a player head.
+    #[serde(alias = "minecraft:fill_player_head")]
+    FillPlayerHead {
+        /// Specifies a player to be used for the player head.
+        entity: WhichEntity,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Limits the count of every item stack.
+    #[serde(alias = "minecraft:limit_count")]
+    LimitCount {
+        /// An exact limit to use, or a range of number providers.
+        limit: Ranged2<isize, NumberProviderValue<isize>>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adjusts the stack size based on the level of the Looting enchantment on the `killer`
+    /// entity.
+    #[serde(alias = "minecraft:looting_enchant")]
+    LootingEnchant {
+        /// Number Provider. Specifies the number of additional items per level of looting. Note
+        /// the number may be fractional, rounded after multiplying by the looting level.
+        count: NumberProviderValue<Number>,
+        /// Specifies the maximum amount of items in the stack after the looting calculation. If
+        /// the value is 0, no limit is applied.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<isize>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Add attribute modifiers to the item.
+    #[serde(alias = "minecraft:set_attributes")]
+    SetAttributes {
+        /// The attribute modifiers to add.
+        modifiers: Vec<AttributeModifier>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets tags needed for banner patterns.
+    #[serde(alias = "minecraft:set_banner_pattern")]
+    SetBannerPattern {
+        /// List of patterns.
+        patterns: Vec<BannerPattern>,
+        /// If true, new patterns are appended to existing ones.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        append: bool,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the contents of a container block item to a list of entries.
+    #[serde(alias = "minecraft:set_contents")]
+    SetContents {
+        /// The entries to use as contents.
+        entries: Vec<Entry>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the stack size.
+    #[serde(alias = "minecraft:set_count")]
+    SetCount {
+        /// Number Provider. Specifies the stack size to set.
+        count: NumberProviderValue<Number>,
+        /// If true, change will be relative to current count.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        add: bool,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the item's damage value (durability) for tools.
+    #[serde(alias = "minecraft:set_damage")]
+    SetDamage {
+        /// Number Provider. Specifies the damage fraction to set (1.0 is undamaged, 0.0 is zero
+        /// durability left).
+        damage: NumberProviderValue<Number>,
+        /// If true, change will be relative to current damage.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        add: bool,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the item's enchantments.
+    #[serde(alias = "minecraft:set_enchantments")]
+    SetEnchantments {
+        /// Enchantments to add.
+        ///
+        /// Item: Key name is the enchantment ID while the value is a number provider
+        /// specifying the enchantment power.
+        enchantments: BTreeMap<ResourceLocation, NumberProviderValue<isize>>,
+        /// If true, change will be relative to current level.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        add: bool,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the loot table for a container (chest etc.).
+    #[serde(alias = "minecraft:set_loot_table")]
+    SetLootTable {
+        /// Specifies the resource location of the loot table to be used.
+        name: ResourceLocation,
+        /// Optional. Specifies the loot table seed. If absent or set to 0, the seed won't be
+        /// put into the NBT, and a random seed will be used when opening the container.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<i64>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adds lore to the item.
+    #[serde(alias = "minecraft:set_lore")]
+    SetLore {
+        /// List of JSON text components. Each list entry represents one line of the lore.
+        lore: Vec<TextComponent>,
+        /// Specifies the entity to act as `@s` when referenced in the JSON text component.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<WhichEntity>,
+        /// If true, replaces all existing lines of lore, if false appends the list.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        replace: bool,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adds display name of the item.
+    #[serde(alias = "minecraft:set_name")]
+    SetName {
+        /// A JSON text component name, allowing color, translations, etc.
+        name: Box<TextComponent>,
+        /// Specifies the entity to act as `@s` when referenced in the JSON text component.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entity: Option<WhichEntity>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adds NBT data to an item.
+    #[serde(alias = "minecraft:set_nbt")]
+    SetNbt {
+        /// Tag string to add, similar to those used by commands. Note that the first bracket is
+        /// required and quotation marks need to be escaped using a backslash (`\`).
+        tag: String,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Sets the status effects for suspicious stew. Fails if invoked on an item that is not
+    /// suspicious stew.
+    #[serde(alias = "minecraft:set_stew_effect")]
+    SetStewEffect {
+        /// The effects to apply.
+        effects: Vec<StewEffect>,
+        /// Determines conditions for this function to be applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+}
+
+impl LootFunction {
+    /// Conditions for this function to be applied.
+    pub fn conditions(&self) -> &[Predicate] {
+        match self {
+            LootFunction::ApplyBonus { conditions, .. }
+            | LootFunction::CopyName { conditions, .. }
+            | LootFunction::CopyNbt { conditions, .. }
+            | LootFunction::CopyState { conditions, .. }
+            | LootFunction::EnchantRandomly { conditions, .. }
+            | LootFunction::EnchantWithLevels { conditions, .. }
+            | LootFunction::ExplorationMap { conditions, .. }
+            | LootFunction::ExplosionDecay { conditions, .. }
+            | LootFunction::FurnaceSmelt { conditions, .. }
+            | LootFunction::FillPlayerHead { conditions, .. }
+            | LootFunction::LimitCount { conditions, .. }
+            | LootFunction::LootingEnchant { conditions, .. }
+            | LootFunction::SetAttributes { conditions, .. }
+            | LootFunction::SetBannerPattern { conditions, .. }
+            | LootFunction::SetContents { conditions, .. }
+            | LootFunction::SetCount { conditions, .. }
+            | LootFunction::SetDamage { conditions, .. }
+            | LootFunction::SetEnchantments { conditions, .. }
+            | LootFunction::SetLootTable { conditions, .. }
+            | LootFunction::SetLore { conditions, .. }
+            | LootFunction::SetName { conditions, .. }
+            | LootFunction::SetNbt { conditions, .. }
+            | LootFunction::SetStewEffect { conditions, .. } => conditions,
+        }
+    }
+}
+
+/// Bonus formula for [`LootFunction::ApplyBonus`], together with its parameters.
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "formula", content = "parameters")]
+pub enum BonusFormula {
+    /// Binomial distribution with `n = level + extra` and `p = probability`.
+    #[serde(rename = "minecraft:binomial_with_bonus_count")]
+    BinomialWithBonusCount {
+        /// Extra value added to the enchantment level.
+        extra: isize,
+        /// Probability of success on an individual trial.
+        probability: Number,
+    },
+    /// Uniform distribution from `0` to `level * bonusMultiplier`.
+    #[serde(rename = "minecraft:uniform_bonus_count")]
+    UniformBonusCount {
+        /// Multiplier of the enchantment level.
+        #[serde(rename = "bonusMultiplier")]
+        bonus_multiplier: Number,
+    },
+    /// Special function for ore drops trying to emulate fortune behavior.
+    #[serde(rename = "minecraft:ore_drops")]
+    OreDrops,
+}
+
+/// Source for [`LootFunction::CopyName`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum NameSource {
+    This,
+    Killer,
+    KillerPlayer,
+    BlockEntity,
+}
+
+/// Source for [`LootFunction::CopyNbt`].
+pub type NbtSource = Either<NameSource, NbtProvider>;
+
+/// NBT provider for [`LootFunction::CopyNbt`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NbtProvider {
+    /// Copies from an entity or block entity in the loot context.
+    Context {
+        /// One of `this`, `killer`, `killer_player` or `block_entity`.
+        target: NameSource,
+    },
+    /// Copies from command storage.
+    Storage {
+        /// A resource location specifying the storage ID.
+        source: ResourceLocation,
+    },
+}
+
+/// A copy operation for [`LootFunction::CopyNbt`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct CopyNbtOperation {
+    /// The NBT path to copy from.
+    pub source: String,
+    /// The NBT path to copy to, starting from the item's `tag` tag.
+    pub target: String,
+    /// Can be `replace` to replace any existing contents of the target, `append` to append to
+    /// a list, or `merge` to merge into a compound tag.
+    pub op: CopyNbtOp,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Operation kind for a [`CopyNbtOperation`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum CopyNbtOp {
+    Replace,
+    Append,
+    Merge,
+}
+
+/// An attribute modifier for [`LootFunction::SetAttributes`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct AttributeModifier {
+    /// Name of the modifier.
+    pub name: String,
+    /// The resource location of the attribute this modifier is to act upon.
+    pub attribute: ResourceLocation,
+    /// The operation of this attribute modifier.
+    pub operation: AttributeOperation,
+    /// Number Provider. Specifies the amount of the modifier.
+    pub amount: NumberProviderValue<Number>,
+    /// Optional. UUID of the modifier following. If none specified, a new UUID is generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Slots the item must be in for the modifier to take effect. This value can be one of the
+    /// following: `mainhand`, `offhand`, `feet`, `legs`, `chest`, or `head`. If a list is
+    /// given, one of the listed slots is chosen randomly.
+    pub slot: Either<String, Vec<String>>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Operation of an [`AttributeModifier`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum AttributeOperation {
+    Addition,
+    MultiplyBase,
+    MultiplyTotal,
+}
+
+/// A banner pattern for [`LootFunction::SetBannerPattern`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct BannerPattern {
+    /// The pattern type.
+    pub pattern: String,
+    /// The color for this pattern.
+    pub color: String,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// A status effect for [`LootFunction::SetStewEffect`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct StewEffect {
+    /// The effect ID.
+    pub r#type: ResourceLocation,
+    /// Number Provider. The duration of the effect.
+    pub duration: NumberProviderValue<isize>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
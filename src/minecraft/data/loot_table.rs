@@ -0,0 +1,243 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Loot tables are technical JSON files that are used to dictate what items should generate in
+//! various situations, such as what items should be in naturally generated containers, what
+//! items should drop when breaking a block or killing a mob, what items can be fished, and more.
+//!
+//! Inside a data pack, a loot table is located within `data/<namespace>/loot_tables`.
+//!
+//! ```
+//! # use minecraft_json::assert_equiv_pretty_protected;
+//! # use minecraft_json::minecraft::data::loot_table::{LootTable, Pool, Entry, EntryProperties};
+//! # use minecraft_json::minecraft::data::predicate::Predicate;
+//! # use minecraft_json::minecraft::common::{Either, Extra};
+//! assert_equiv_pretty_protected!(r#"{
+//!   "type": "minecraft:block",
+//!   "pools": [
+//!     {
+//!       "rolls": 1,
+//!       "entries": [
+//!         {
+//!           "type": "minecraft:item",
+//!           "name": "minecraft:dirt"
+//!         }
+//!       ],
+//!       "conditions": [
+//!         {
+//!           "condition": "survives_explosion"
+//!         }
+//!       ]
+//!     }
+//!   ]
+//! }"#, LootTable {
+//!     r#type: Some("minecraft:block".parse().unwrap()),
+//!     functions: Vec::new(),
+//!     pools: vec![Pool {
+//!         rolls: Either::Left(1.into()),
+//!         bonus_rolls: None,
+//!         entries: vec![Entry::Item {
+//!             name: "minecraft:dirt".parse().unwrap(),
+//!             properties: EntryProperties::default(),
+//!         }],
+//!         conditions: vec![Predicate::SurvivesExplosion { extra: Extra::default() }],
+//!         functions: Vec::new(),
+//!         extra: Extra::default(),
+//!     }],
+//!     extra: Extra::default(),
+//! });
+//! ```
+
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::Number;
+use crate::defaults;
+use crate::minecraft::common::{NumberProviderValue, ResourceLocation, Extra};
+use crate::minecraft::data::predicate::Predicate;
+use crate::minecraft::data::item_modifier::LootFunction;
+
+/// A loot table JSON file.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct LootTable {
+    /// Optional type of the loot table. Must be one of `empty` if the loot table does not generate
+    /// any loot, `entity` for loot from an entity, `block` for loot from a block, `chest` for a
+    /// treasure chest, `fishing` for a fishing loot table, `gift` for a cat or villager gift,
+    /// `advancement_reward` if it's used as a reward for an advancement, `barter` for loot from
+    /// bartering with piglins, `command` for `/loot` command, `selector` for predicate files,
+    /// `advancement_entity` for advancement criteria, or `generic` if none of the above apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<ResourceLocation>,
+    /// Applies functions to all item stacks produced by this table. Functions are applied in
+    /// order, so for example looting_enchant must be after set_count to work correctly.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<LootFunction>,
+    /// A list of all pools for this loot table. Each pool used generates items from its list of
+    /// items based on the number of rolls. Pools are applied in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pools: Vec<Pool>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// A loot pool.
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Pool {
+    /// Number Provider. Specifies the number of rolls on the pool.
+    pub rolls: NumberProviderValue<Number>,
+    /// Number Provider. Specifies the number of bonus rolls on the pool per point of luck.
+    /// Rounded down after multiplying.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bonus_rolls: Option<NumberProviderValue<Number>>,
+    /// A list of all things that can be produced by this pool. One entry is chosen per roll as
+    /// a weighted random selection from all entries without failing conditions.
+    pub entries: Vec<Entry>,
+    /// Determines conditions for this pool to be used. If multiple conditions are specified, all
+    /// must pass.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<Predicate>,
+    /// Applies functions to all item stacks produced by this pool. Functions are applied in
+    /// order, so for example looting_enchant must be after set_count to work correctly.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<LootFunction>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// An entry in a loot [`Pool`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type")]
+pub enum Entry {
+    /// Adds an item to the pool.
+    #[serde(rename = "minecraft:item", alias = "item")]
+    Item {
+        /// ID name of the item to be produced, e.g. `diamond`. The default, if not changed by
+        /// functions, is a stack of 1 of the default instance of the item.
+        name: ResourceLocation,
+        /// Common properties for singleton entries.
+        #[serde(flatten)]
+        properties: EntryProperties,
+    },
+    /// Adds the contents of an item tag to the pool.
+    #[serde(rename = "minecraft:tag", alias = "tag")]
+    Tag {
+        /// Item tag to be used, e.g. `arrows`.
+        name: ResourceLocation,
+        /// If set to `true`, it chooses one item of the tag, each with the same weight and
+        /// quality. If `false`, it generates one of each of the items in the tag.
+        #[serde(default, skip_serializing_if = "defaults::is_false")]
+        expand: bool,
+        /// Common properties for singleton entries.
+        #[serde(flatten)]
+        properties: EntryProperties,
+    },
+    /// Produces the items of another loot table.
+    #[serde(rename = "minecraft:loot_table", alias = "loot_table")]
+    LootTable {
+        /// Loot table to be used, e.g. `gameplay/fishing/junk`.
+        name: ResourceLocation,
+        /// Common properties for singleton entries.
+        #[serde(flatten)]
+        properties: EntryProperties,
+    },
+    /// Produces block specific drops: `contents` for block entity contents, or `self` for banners
+    /// and player skulls.
+    #[serde(rename = "minecraft:dynamic", alias = "dynamic")]
+    Dynamic {
+        /// Can be `contents` for block entity contents or `self` for banners and player skulls.
+        name: ResourceLocation,
+        /// Common properties for singleton entries.
+        #[serde(flatten)]
+        properties: EntryProperties,
+    },
+    /// Produces nothing, but still takes part in the weighted random selection.
+    #[serde(rename = "minecraft:empty", alias = "empty")]
+    Empty {
+        /// Common properties for singleton entries.
+        #[serde(flatten)]
+        properties: EntryProperties,
+    },
+    /// Adds all the entries in `children` to the pool, if the conditions pass.
+    #[serde(rename = "minecraft:group", alias = "group")]
+    Group {
+        /// The list of entries to be grouped.
+        children: Vec<Entry>,
+        /// Determines conditions for this entry to be used. If multiple conditions are
+        /// specified, all must pass.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+    },
+    /// Tests the conditions of the entries in `children` in order, and adds only the first one
+    /// that passes to the pool.
+    #[serde(rename = "minecraft:alternatives", alias = "alternatives")]
+    Alternatives {
+        /// The list of entries to be tested.
+        children: Vec<Entry>,
+        /// Determines conditions for this entry to be used. If multiple conditions are
+        /// specified, all must pass.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+    },
+    /// Adds the entries in `children` to the pool in order, until one of them fails its
+    /// conditions.
+    #[serde(rename = "minecraft:sequence", alias = "sequence")]
+    Sequence {
+        /// The list of entries to be added in sequence.
+        children: Vec<Entry>,
+        /// Determines conditions for this entry to be used. If multiple conditions are
+        /// specified, all must pass.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conditions: Vec<Predicate>,
+    },
+}
+
+/// Properties common to all singleton [`Entry`]s, i.e. entries other than [`Entry::Group`],
+/// [`Entry::Alternatives`] and [`Entry::Sequence`].
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct EntryProperties {
+    /// Determines conditions for this entry to be used. If multiple conditions are specified,
+    /// all must pass.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<Predicate>,
+    /// Applies functions to the item stack or item stacks being produced. Functions are applied
+    /// in order, so for example looting_enchant must be after set_count to work correctly.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<LootFunction>,
+    /// Determines how often the loot entry is chosen out of all the entries in the pool. Entries
+    /// with higher weights are used more often (chance is `this entry's weight ⁄ total of all
+    /// considered entries' weights`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<isize>,
+    /// Modifies the loot entry's weight based on the killing/opening/fishing player's luck
+    /// attribute. Formula is `floor(weight + (quality * generic.luck))`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<isize>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
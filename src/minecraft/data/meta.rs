@@ -34,7 +34,11 @@
 //! });
 //! ```
 
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use serde::{Serialize, Deserialize};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
 
 use crate::minecraft::text::TextComponent;
 
@@ -42,6 +46,7 @@ use crate::minecraft::text::TextComponent;
 /// root directory of the data pack, which contains data in JSON format.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum McMeta {
     /// Holds the data pack information.
@@ -56,6 +61,15 @@ pub enum McMeta {
     }
 }
 
+impl McMeta {
+    /// The declared pack version.
+    pub fn pack_format(&self) -> i64 {
+        match self {
+            McMeta::Pack { pack_format, .. } => *pack_format,
+        }
+    }
+}
+
 /// Known `pack_format` field for [`McMeta`].
 pub mod pack_format {
     /// Minecraft 1.13–1.14.4.
@@ -64,6 +78,122 @@ pub mod pack_format {
     pub const VER_5: i64 = 5;
     /// Minecraft 1.16.2–1.16.5.
     pub const VER_6: i64 = 6;
-    /// Minecraft 1.17.
+    /// Minecraft 1.17–1.17.1.
     pub const VER_7: i64 = 7;
+    /// Minecraft 1.18–1.18.1.
+    pub const VER_8: i64 = 8;
+    /// Minecraft 1.18.2.
+    pub const VER_9: i64 = 9;
+    /// Minecraft 1.19–1.19.3.
+    pub const VER_10: i64 = 10;
+    /// Minecraft 1.19.4.
+    pub const VER_12: i64 = 12;
+    /// Minecraft 1.20–1.20.1.
+    pub const VER_15: i64 = 15;
+    /// Minecraft 1.20.2.
+    pub const VER_18: i64 = 18;
+    /// Minecraft 1.20.3–1.20.4.
+    pub const VER_26: i64 = 26;
+    /// Minecraft 1.20.5–1.20.6.
+    pub const VER_41: i64 = 41;
+    /// Minecraft 1.21–1.21.1.
+    pub const VER_48: i64 = 48;
+    /// Minecraft 1.21.2–1.21.3.
+    pub const VER_57: i64 = 57;
+    /// Minecraft 1.21.4.
+    pub const VER_61: i64 = 61;
+    /// Minecraft 1.21.5.
+    pub const VER_71: i64 = 71;
+}
+
+/// A release version of Minecraft, e.g. `1.20.5`.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::meta::{GameVersion, pack_format};
+/// let version: GameVersion = "1.16.5".parse().unwrap();
+/// assert_eq!(version.pack_format(), Some(pack_format::VER_6));
+/// assert_eq!("1.21".parse::<GameVersion>().unwrap().to_string(), "1.21");
+/// assert_eq!(GameVersion::first_with_pack_format(pack_format::VER_41),
+///            Some(GameVersion::new(1, 20, 5)));
+/// assert_eq!("1.12.2".parse::<GameVersion>().unwrap().pack_format(), None);
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Hash)]
+pub struct GameVersion {
+    /// Major version, always 1 so far.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version, 0 if absent.
+    pub patch: u32,
+}
+
+/// First release for each data pack `pack_format`, in order.
+const RELEASES: &[(GameVersion, i64)] = &[
+    (GameVersion::new(1, 13, 0), pack_format::VER_4),
+    (GameVersion::new(1, 15, 0), pack_format::VER_5),
+    (GameVersion::new(1, 16, 2), pack_format::VER_6),
+    (GameVersion::new(1, 17, 0), pack_format::VER_7),
+    (GameVersion::new(1, 18, 0), pack_format::VER_8),
+    (GameVersion::new(1, 18, 2), pack_format::VER_9),
+    (GameVersion::new(1, 19, 0), pack_format::VER_10),
+    (GameVersion::new(1, 19, 4), pack_format::VER_12),
+    (GameVersion::new(1, 20, 0), pack_format::VER_15),
+    (GameVersion::new(1, 20, 2), pack_format::VER_18),
+    (GameVersion::new(1, 20, 3), pack_format::VER_26),
+    (GameVersion::new(1, 20, 5), pack_format::VER_41),
+    (GameVersion::new(1, 21, 0), pack_format::VER_48),
+    (GameVersion::new(1, 21, 2), pack_format::VER_57),
+    (GameVersion::new(1, 21, 4), pack_format::VER_61),
+    (GameVersion::new(1, 21, 5), pack_format::VER_71),
+];
+
+impl GameVersion {
+    /// Create a game version.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> GameVersion {
+        GameVersion { major, minor, patch }
+    }
+
+    /// The data pack `pack_format` of this version, `None` if data packs are not supported.
+    /// Versions newer than all known ones get the newest known `pack_format`.
+    pub fn pack_format(self) -> Option<i64> {
+        RELEASES.iter().rev().find(|(v, _)| *v <= self).map(|(_, f)| *f)
+    }
+
+    /// The first release using a `pack_format`, `None` if unknown.
+    pub fn first_with_pack_format(pack_format: i64) -> Option<GameVersion> {
+        RELEASES.iter().find(|(_, f)| *f == pack_format).map(|(v, _)| *v)
+    }
+}
+
+impl Display for GameVersion {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if self.patch != 0 { write!(f, ".{}", self.patch)?; }
+        Ok(())
+    }
+}
+
+/// Error in parsing a [`GameVersion`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct GameVersionError(pub String);
+
+impl Display for GameVersionError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "invalid game version '{}'", self.0)
+    }
+}
+
+impl std::error::Error for GameVersionError {}
+
+impl FromStr for GameVersion {
+    type Err = GameVersionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || GameVersionError(s.to_string());
+        let mut parts = s.split('.').map(|p| p.parse::<u32>().map_err(|_| err()));
+        let major = parts.next().ok_or_else(err)??;
+        let minor = parts.next().ok_or_else(err)??;
+        let patch = parts.next().transpose()?.unwrap_or(0);
+        if parts.next().is_some() { return Err(err()); }
+        Ok(GameVersion { major, minor, patch })
+    }
 }
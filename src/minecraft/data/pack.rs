@@ -0,0 +1,588 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Loading and writing whole data packs.
+//!
+//! A data pack is a directory (or archive) with a `pack.mcmeta` file at its root, and resources
+//! under `data/<namespace>/<kind>/<path>`. Every resource is identified by the namespaced ID
+//! `<namespace>:<path>`, with `<path>` excluding the file extension.
+//!
+//! ```
+//! # use std::fs;
+//! # use minecraft_json::minecraft::data::pack::DataPack;
+//! # let root = std::env::temp_dir().join("minecraft-json-doctest-pack-load");
+//! # let _ = fs::remove_dir_all(&root);
+//! # fs::create_dir_all(root.join("data/demo/predicates")).unwrap();
+//! # fs::write(root.join("pack.mcmeta"),
+//! #           r#"{"pack": {"description": "demo", "pack_format": 7}}"#).unwrap();
+//! # fs::write(root.join("data/demo/predicates/raining.json"),
+//! #           r#"{"condition": "weather_check", "raining": true}"#).unwrap();
+//! # fs::write(root.join("data/demo/predicates/broken.json"), "{").unwrap();
+//! let (pack, errors) = DataPack::load(&root).unwrap();
+//! assert!(pack.predicates.contains_key("demo:raining"));
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].path, "data/demo/predicates/broken.json");
+//! # fs::remove_dir_all(&root).unwrap();
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use derivative::Derivative;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::minecraft::data::meta::{McMeta, pack_format};
+use crate::minecraft::data::advancement::Advancement;
+use crate::minecraft::data::predicate::PredicateFile;
+use crate::minecraft::data::loot_table::LootTable;
+use crate::minecraft::data::item_modifier::ItemModifier;
+use crate::minecraft::data::recipe::Recipe;
+use crate::minecraft::data::tag::{Tags, TagKind};
+use crate::minecraft::data::version::{self, VersionIssue};
+use crate::minecraft::error::ParseError;
+use crate::minecraft::lenient::{self, DuplicateKey, Parsed};
+use crate::minecraft::span;
+
+#[cfg(feature = "zip")]
+mod archive;
+
+/// Kinds of resources supported in a [`DataPack`].
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Hash)]
+pub enum ResourceKind {
+    /// Advancements, in `advancements`.
+    Advancement,
+    /// Predicates, in `predicates`.
+    Predicate,
+    /// Loot tables, in `loot_tables`.
+    LootTable,
+    /// Item modifiers, in `item_modifiers`.
+    ItemModifier,
+    /// Recipes, in `recipes`.
+    Recipe,
+    /// Functions, in `functions`.
+    Function,
+    /// Tags, in `tags/<kind>`.
+    Tag(TagKind),
+}
+
+impl ResourceKind {
+    /// The directory for this kind of resource under `data/<namespace>`, before Minecraft 1.21.
+    pub fn directory(self) -> String {
+        match self {
+            ResourceKind::Advancement => "advancements".to_string(),
+            ResourceKind::Predicate => "predicates".to_string(),
+            ResourceKind::LootTable => "loot_tables".to_string(),
+            ResourceKind::ItemModifier => "item_modifiers".to_string(),
+            ResourceKind::Recipe => "recipes".to_string(),
+            ResourceKind::Function => "functions".to_string(),
+            ResourceKind::Tag(kind) => format!("tags/{}", kind.directory()),
+        }
+    }
+
+    /// The directory for this kind of resource under `data/<namespace>`, as of a
+    /// [`pack_format`]. Directory names are singular since Minecraft 1.21.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::pack::ResourceKind;
+    /// # use minecraft_json::minecraft::data::meta::pack_format;
+    /// # use minecraft_json::minecraft::data::tag::TagKind;
+    /// assert_eq!(ResourceKind::LootTable.directory_for(pack_format::VER_7), "loot_tables");
+    /// assert_eq!(ResourceKind::LootTable.directory_for(pack_format::VER_48), "loot_table");
+    /// assert_eq!(ResourceKind::Tag(TagKind::Items).directory_for(pack_format::VER_48), "tags/item");
+    /// ```
+    pub fn directory_for(self, pack_format: i64) -> String {
+        if pack_format < pack_format::VER_48 { return self.directory(); }
+        match self {
+            ResourceKind::Advancement => "advancement".to_string(),
+            ResourceKind::Predicate => "predicate".to_string(),
+            ResourceKind::LootTable => "loot_table".to_string(),
+            ResourceKind::ItemModifier => "item_modifier".to_string(),
+            ResourceKind::Recipe => "recipe".to_string(),
+            ResourceKind::Function => "function".to_string(),
+            ResourceKind::Tag(kind) => format!("tags/{}", kind.directory_for(pack_format)),
+        }
+    }
+
+    /// The file extension for this kind of resource.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ResourceKind::Function => "mcfunction",
+            _ => "json",
+        }
+    }
+
+    /// The path of a resource of this kind relative to the pack root, given its namespaced ID.
+    /// IDs without a namespace are in the default `minecraft` namespace.
+    pub fn path_of(self, id: &str) -> String {
+        let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+        format!("data/{}/{}/{}.{}", namespace, self.directory(), path, self.extension())
+    }
+
+    /// The path of a resource of this kind relative to the pack root as of a [`pack_format`],
+    /// given its namespaced ID.
+    pub fn path_for(self, id: &str, pack_format: i64) -> String {
+        let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+        format!("data/{}/{}/{}.{}", namespace, self.directory_for(pack_format), path, self.extension())
+    }
+
+    /// Find the kind and namespaced ID of a resource given its path relative to the pack root.
+    /// Both the plural and the singular directory names are accepted.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::pack::ResourceKind;
+    /// # use minecraft_json::minecraft::data::tag::TagKind;
+    /// assert_eq!(ResourceKind::from_path("data/demo/tags/blocks/logs.json"),
+    ///            Some((ResourceKind::Tag(TagKind::Blocks), "demo:logs".to_string())));
+    /// assert_eq!(ResourceKind::from_path("data/demo/functions/a/b.mcfunction"),
+    ///            Some((ResourceKind::Function, "demo:a/b".to_string())));
+    /// assert_eq!(ResourceKind::from_path("data/demo/function/a/b.mcfunction"),
+    ///            Some((ResourceKind::Function, "demo:a/b".to_string())));
+    /// assert_eq!(ResourceKind::from_path("data/demo/structures/house.nbt"), None);
+    /// ```
+    pub fn from_path(path: &str) -> Option<(ResourceKind, String)> {
+        let rest = path.strip_prefix("data/")?;
+        let (namespace, rest) = rest.split_once('/')?;
+        let (dir, rest) = rest.split_once('/')?;
+        let (kind, rest) = match dir {
+            "advancements" | "advancement" => (ResourceKind::Advancement, rest),
+            "predicates" | "predicate" => (ResourceKind::Predicate, rest),
+            "loot_tables" | "loot_table" => (ResourceKind::LootTable, rest),
+            "item_modifiers" | "item_modifier" => (ResourceKind::ItemModifier, rest),
+            "recipes" | "recipe" => (ResourceKind::Recipe, rest),
+            "functions" | "function" => (ResourceKind::Function, rest),
+            "tags" => {
+                let (tag_dir, rest) = rest.split_once('/')?;
+                (ResourceKind::Tag(TagKind::from_directory(tag_dir)?), rest)
+            }
+            _ => return None,
+        };
+        let name = rest.strip_suffix(kind.extension())?.strip_suffix('.')?;
+        if namespace.is_empty() || name.is_empty() { return None; }
+        Some((kind, format!("{}:{}", namespace, name)))
+    }
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ResourceKind::Advancement => write!(f, "advancement"),
+            ResourceKind::Predicate => write!(f, "predicate"),
+            ResourceKind::LootTable => write!(f, "loot table"),
+            ResourceKind::ItemModifier => write!(f, "item modifier"),
+            ResourceKind::Recipe => write!(f, "recipe"),
+            ResourceKind::Function => write!(f, "function"),
+            ResourceKind::Tag(kind) => write!(f, "{} tag", kind.directory()),
+        }
+    }
+}
+
+/// Error in loading a single file of a data pack.
+#[derive(Debug)]
+pub struct LoadError {
+    /// Path of the file relative to the pack root, separated by `/`.
+    pub path: String,
+    /// The error itself.
+    pub error: LoadErrorKind,
+}
+
+/// Kinds of [`LoadError`]s.
+#[derive(Debug)]
+pub enum LoadErrorKind {
+    /// Failed to read the file.
+    Io(io::Error),
+    /// Failed to parse or deserialize the file.
+    Json(ParseError),
+    /// The file uses a feature not valid in the `pack_format` declared in `pack.mcmeta`. The file
+    /// is still loaded.
+    Version(VersionIssue),
+    /// The file has a duplicate key in some object. The file is still loaded, with the last value
+    /// for the key, as in the game.
+    DuplicateKey(DuplicateKey),
+    /// Failed to read the file from an archive.
+    #[cfg(feature = "zip")]
+    Zip(zip::result::ZipError),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.error)
+    }
+}
+
+impl Display for LoadErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            LoadErrorKind::Io(err) => err.fmt(f),
+            LoadErrorKind::Json(err) => err.fmt(f),
+            LoadErrorKind::Version(err) => err.fmt(f),
+            LoadErrorKind::DuplicateKey(err) => err.fmt(f),
+            #[cfg(feature = "zip")]
+            LoadErrorKind::Zip(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.error {
+            LoadErrorKind::Io(err) => Some(err),
+            LoadErrorKind::Json(err) => Some(err),
+            LoadErrorKind::Version(err) => Some(err),
+            LoadErrorKind::DuplicateKey(err) => Some(err),
+            #[cfg(feature = "zip")]
+            LoadErrorKind::Zip(err) => Some(err),
+        }
+    }
+}
+
+/// Result of loading a [`DataPack`]: the pack together with errors in individual files, or a
+/// fatal error for the whole pack.
+pub type LoadResult = Result<(DataPack, Vec<LoadError>), LoadError>;
+
+/// A data pack, with all supported resources indexed by their namespaced IDs.
+#[derive(Derivative, Debug)]
+#[derivative(PartialEq)]
+pub struct DataPack {
+    /// The `pack.mcmeta` file.
+    pub meta: McMeta,
+    /// Advancements.
+    pub advancements: BTreeMap<String, Advancement>,
+    /// Predicates.
+    pub predicates: BTreeMap<String, PredicateFile>,
+    /// Loot tables.
+    pub loot_tables: BTreeMap<String, LootTable>,
+    /// Item modifiers.
+    pub item_modifiers: BTreeMap<String, ItemModifier>,
+    /// Recipes.
+    pub recipes: BTreeMap<String, Recipe>,
+    /// Functions, as their source text.
+    pub functions: BTreeMap<String, String>,
+    /// Tags of all kinds.
+    pub tags: Tags,
+    /// Paths of the files resources were loaded from, relative to the pack root, by kind and ID.
+    /// Resources not loaded from files have none. Ignored when comparing packs.
+    #[derivative(PartialEq = "ignore")]
+    pub sources: BTreeMap<(ResourceKind, String), String>,
+}
+
+impl DataPack {
+    /// Create an empty data pack.
+    pub fn new(meta: McMeta) -> DataPack {
+        DataPack {
+            meta,
+            advancements: BTreeMap::new(),
+            predicates: BTreeMap::new(),
+            loot_tables: BTreeMap::new(),
+            item_modifiers: BTreeMap::new(),
+            recipes: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            tags: Tags::default(),
+            sources: BTreeMap::new(),
+        }
+    }
+
+    /// Load a data pack from a directory containing `pack.mcmeta`.
+    ///
+    /// Failure to load `pack.mcmeta` is fatal; errors in individual resource files are collected
+    /// and returned alongside the pack, and the offending files are skipped.
+    pub fn load(root: impl AsRef<Path>) -> LoadResult {
+        let root = root.as_ref();
+        let meta = fs::read_to_string(root.join("pack.mcmeta"))
+            .map_err(|err| LoadError { path: "pack.mcmeta".to_string(), error: LoadErrorKind::Io(err) })?;
+        let mut pack = DataPack::new(parse("pack.mcmeta", &meta)?);
+        let mut errors = Vec::new();
+        let mut files = Vec::new();
+        collect_files(&root.join("data"), "data", &mut files, &mut errors);
+        files.sort();
+        for (path, file) in files {
+            match fs::read_to_string(&file) {
+                Ok(contents) => pack.add_file(&path, &contents, &mut errors),
+                Err(err) => errors.push(LoadError { path, error: LoadErrorKind::Io(err) }),
+            }
+        }
+        Ok((pack, errors))
+    }
+
+    /// Load a data pack from either a directory or a zip archive (with the `zip` feature).
+    pub fn open(path: impl AsRef<Path>) -> LoadResult {
+        let path = path.as_ref();
+        if path.is_dir() { return DataPack::load(path); }
+        #[cfg(feature = "zip")]
+        {
+            let file = fs::File::open(path).map_err(|err| LoadError {
+                path: path.display().to_string(),
+                error: LoadErrorKind::Io(err),
+            })?;
+            DataPack::load_zip(io::BufReader::new(file))
+        }
+        #[cfg(not(feature = "zip"))]
+        Err(LoadError {
+            path: path.display().to_string(),
+            error: LoadErrorKind::Io(io::Error::new(io::ErrorKind::InvalidInput, "not a directory")),
+        })
+    }
+
+    /// Load all data packs in a directory, e.g. the `datapacks` folder of a world, keyed by their
+    /// file names. Only directories and (with the `zip` feature) `.zip` files are considered.
+    pub fn load_all(dir: impl AsRef<Path>) -> io::Result<BTreeMap<String, LoadResult>> {
+        let mut packs = BTreeMap::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_zip = cfg!(feature = "zip")
+                && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+            if path.is_dir() || is_zip {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                packs.insert(name, DataPack::open(&path));
+            }
+        }
+        Ok(packs)
+    }
+
+    /// Add a single resource file to this pack, given its path relative to the pack root. Files
+    /// not recognised as supported resources are ignored. JSON files are read as the game does,
+    /// see [`lenient`]. Features not valid in the declared `pack_format` are reported as
+    /// [`LoadErrorKind::Version`], see [`version`], and duplicate keys as
+    /// [`LoadErrorKind::DuplicateKey`].
+    pub fn add_file(&mut self, path: &str, contents: &str, errors: &mut Vec<LoadError>) {
+        let (kind, id) = match ResourceKind::from_path(path) {
+            Some(res) => res,
+            None => return,
+        };
+        let error = |error| LoadError { path: path.to_string(), error };
+        let pack_format = self.meta.pack_format();
+        errors.extend(version::check_path(path, pack_format).map(|issue| error(LoadErrorKind::Version(issue))));
+        if kind == ResourceKind::Function {
+            self.functions.insert(id.clone(), contents.to_string());
+            self.sources.insert((kind, id), path.to_string());
+            return;
+        }
+        // read the text only once, for the version checks, duplicate keys and the model
+        let mut parsed = match lenient::parse(contents) {
+            Ok(parsed) => parsed,
+            Err(err) => return errors.push(error(LoadErrorKind::Json(err.into()))),
+        };
+        for issue in version::check_value(kind, &parsed.value, pack_format) {
+            errors.push(error(LoadErrorKind::Version(issue)));
+        }
+        for duplicate in std::mem::take(&mut parsed.duplicates) {
+            errors.push(error(LoadErrorKind::DuplicateKey(duplicate)));
+        }
+        match self.add_parsed(path, kind, id.clone(), contents, parsed) {
+            Ok(()) => { self.sources.insert((kind, id), path.to_string()); }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    /// Path of the file defining a resource, relative to the pack root: the file it was loaded
+    /// from if any, or else where [`DataPack::to_files`] would write it.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::pack::{DataPack, ResourceKind};
+    /// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+    /// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+    /// let mut pack = DataPack::new(McMeta::Pack {
+    ///     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+    ///     pack_format: pack_format::VER_48,
+    /// });
+    /// let mut errors = Vec::new();
+    /// pack.add_file("data/demo/predicates/a.json", r#"{"condition":"survives_explosion"}"#, &mut errors);
+    /// assert_eq!(pack.source_of(ResourceKind::Predicate, "demo:a"), "data/demo/predicates/a.json");
+    /// assert_eq!(pack.source_of(ResourceKind::Predicate, "demo:b"), "data/demo/predicate/b.json");
+    /// ```
+    pub fn source_of(&self, kind: ResourceKind, id: &str) -> String {
+        match self.sources.get(&(kind, id.to_string())) {
+            Some(path) => path.clone(),
+            None => kind.path_for(id, self.meta.pack_format()),
+        }
+    }
+
+    /// Remove a resource, e.g. to add a changed file again with [`DataPack::add_file`]. Returns
+    /// whether the resource was defined.
+    pub fn remove(&mut self, kind: ResourceKind, id: &str) -> bool {
+        self.sources.remove(&(kind, id.to_string()));
+        match kind {
+            ResourceKind::Advancement => self.advancements.remove(id).is_some(),
+            ResourceKind::Predicate => self.predicates.remove(id).is_some(),
+            ResourceKind::LootTable => self.loot_tables.remove(id).is_some(),
+            ResourceKind::ItemModifier => self.item_modifiers.remove(id).is_some(),
+            ResourceKind::Recipe => self.recipes.remove(id).is_some(),
+            ResourceKind::Function => self.functions.remove(id).is_some(),
+            ResourceKind::Tag(kind) => self.tags.get_mut(kind).remove(id).is_some(),
+        }
+    }
+
+    /// Serialize all resources in this pack into files, paired with their paths relative to the
+    /// pack root. The `pack.mcmeta` file comes first.
+    pub fn to_files(&self) -> serde_json::Result<Vec<(String, String)>> {
+        fn emit<T: Serialize>(files: &mut Vec<(String, String)>, kind: ResourceKind, pack_format: i64,
+                              resources: &BTreeMap<String, T>) -> serde_json::Result<()> {
+            for (id, res) in resources {
+                files.push((kind.path_for(id, pack_format), to_json(res)?));
+            }
+            Ok(())
+        }
+        let pack_format = self.meta.pack_format();
+        let mut files = vec![("pack.mcmeta".to_string(), to_json(&self.meta)?)];
+        emit(&mut files, ResourceKind::Advancement, pack_format, &self.advancements)?;
+        emit(&mut files, ResourceKind::Predicate, pack_format, &self.predicates)?;
+        emit(&mut files, ResourceKind::LootTable, pack_format, &self.loot_tables)?;
+        emit(&mut files, ResourceKind::ItemModifier, pack_format, &self.item_modifiers)?;
+        emit(&mut files, ResourceKind::Recipe, pack_format, &self.recipes)?;
+        for (id, source) in &self.functions {
+            files.push((ResourceKind::Function.path_for(id, pack_format), source.clone()));
+        }
+        for &kind in TagKind::ALL.iter() {
+            for (id, tag) in self.tags.get(kind).iter() {
+                files.push((ResourceKind::Tag(kind).path_for(id, pack_format), to_json(tag)?));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Write this data pack as a directory tree rooted at `root`, i.e. `pack.mcmeta` and all the
+    /// `data/<namespace>/<kind>/<path>` files. Directories are created as needed; existing files
+    /// are overwritten, but files not in this pack are left untouched.
+    ///
+    /// ```
+    /// # use maplit::btreemap;
+    /// # use minecraft_json::minecraft::data::pack::DataPack;
+    /// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+    /// # use minecraft_json::minecraft::data::advancement::{Advancement, Criterion, TriggerSpelling};
+    /// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+    /// # use minecraft_json::minecraft::common::Extra;
+    /// # let root = std::env::temp_dir().join("minecraft-json-doctest-pack-write");
+    /// # let _ = std::fs::remove_dir_all(&root);
+    /// let mut pack = DataPack::new(McMeta::Pack {
+    ///     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+    ///     pack_format: pack_format::VER_7,
+    /// });
+    /// pack.advancements.insert("demo:sleep".to_string(), Advancement {
+    ///     display: None,
+    ///     parent: None,
+    ///     criteria: btreemap! {
+    ///         "slept".to_string() => Criterion::SleptInBed {
+    ///             location: None,
+    ///             player: None,
+    ///             spelling: TriggerSpelling::Namespaced,
+    ///             extra: Extra::default(),
+    ///         },
+    ///     },
+    ///     requirements: Vec::new(),
+    ///     rewards: None,
+    ///     extra: Extra::default(),
+    /// });
+    /// pack.write(&root).unwrap();
+    /// assert_eq!(std::fs::read_to_string(root.join("data/demo/advancements/sleep.json")).unwrap(),
+    ///            r#"{
+    ///   "criteria": {
+    ///     "slept": {
+    ///       "trigger": "minecraft:slept_in_bed",
+    ///       "conditions": {}
+    ///     }
+    ///   }
+    /// }
+    /// "#);
+    /// let (loaded, errors) = DataPack::load(&root).unwrap();
+    /// assert!(errors.is_empty());
+    /// assert_eq!(loaded, pack);
+    /// # std::fs::remove_dir_all(&root).unwrap();
+    /// ```
+    pub fn write(&self, root: impl AsRef<Path>) -> io::Result<()> {
+        write_files(root.as_ref(), self.to_files()?)
+    }
+
+    fn add_parsed(&mut self, path: &str, kind: ResourceKind, id: String, contents: &str,
+                  parsed: Parsed) -> Result<(), LoadError> {
+        match kind {
+            ResourceKind::Advancement => { self.advancements.insert(id, from_parsed(path, contents, parsed)?); }
+            ResourceKind::Predicate => { self.predicates.insert(id, from_parsed(path, contents, parsed)?); }
+            ResourceKind::LootTable => { self.loot_tables.insert(id, from_parsed(path, contents, parsed)?); }
+            ResourceKind::ItemModifier => { self.item_modifiers.insert(id, from_parsed(path, contents, parsed)?); }
+            ResourceKind::Recipe => { self.recipes.insert(id, from_parsed(path, contents, parsed)?); }
+            ResourceKind::Tag(kind) => self.tags.get_mut(kind).insert(&id, from_parsed(path, contents, parsed)?),
+            // not JSON, added by `add_file` directly
+            ResourceKind::Function => {}
+        }
+        Ok(())
+    }
+}
+
+/// Write files paired with their paths relative to `root`, creating directories as needed.
+pub(crate) fn write_files(root: &Path, files: Vec<(String, String)>) -> io::Result<()> {
+    for (path, contents) in files {
+        check_path(&path)?;
+        let path = root.join(path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Reject paths that would escape the pack root or be read differently on another platform.
+pub(crate) fn check_path(path: &str) -> io::Result<()> {
+    if path.split('/').any(|c| c.is_empty() || c == "." || c == ".." || c.contains('\\')) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("invalid resource path '{}'", path)));
+    }
+    Ok(())
+}
+
+pub(crate) fn parse<T: DeserializeOwned>(path: &str, contents: &str) -> Result<T, LoadError> {
+    lenient::from_str(contents)
+        .map_err(|err| LoadError { path: path.to_string(), error: LoadErrorKind::Json(err) })
+}
+
+fn from_parsed<T: DeserializeOwned>(path: &str, contents: &str, parsed: Parsed) -> Result<T, LoadError> {
+    span::from_parsed(contents, parsed).map(|(value, _)| value)
+        .map_err(|err| LoadError { path: path.to_string(), error: LoadErrorKind::Json(err) })
+}
+
+/// Pretty-print a resource, with a trailing newline.
+pub(crate) fn to_json<T: Serialize>(res: &T) -> serde_json::Result<String> {
+    let mut json = serde_json::to_string_pretty(res)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Recursively list all files under `dir`, paired with their paths relative to the pack root.
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<(String, std::path::PathBuf)>,
+                 errors: &mut Vec<LoadError>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+        Err(err) => return errors.push(LoadError { path: prefix.to_string(), error: LoadErrorKind::Io(err) }),
+    };
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                errors.push(LoadError { path: prefix.to_string(), error: LoadErrorKind::Io(err) });
+                continue;
+            }
+        };
+        let path = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => collect_files(&entry.path(), &path, files, errors),
+            Ok(_) => files.push((path, entry.path())),
+            Err(err) => errors.push(LoadError { path, error: LoadErrorKind::Io(err) }),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Reading and writing data packs as zip archives.
+
+use std::io::{Read, Seek, Write};
+use zip::{ZipArchive, ZipWriter, CompressionMethod};
+use zip::result::{ZipError, ZipResult};
+use zip::write::FileOptions;
+use crate::minecraft::data::meta::McMeta;
+use super::{DataPack, LoadError, LoadErrorKind, LoadResult, check_path, parse};
+
+impl DataPack {
+    /// Load a data pack from a zip archive with `pack.mcmeta` at its root.
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use minecraft_json::minecraft::data::pack::DataPack;
+    /// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+    /// # use minecraft_json::minecraft::data::predicate::Predicate;
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
+    /// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+    /// let mut pack = DataPack::new(McMeta::Pack {
+    ///     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+    ///     pack_format: pack_format::VER_7,
+    /// });
+    /// pack.predicates.insert("demo:always".to_string(), Either::Left(Predicate::Inverted {
+    ///     term: Box::new(Predicate::KilledByPlayer { inverse: true, extra: Extra::default() }),
+    ///     extra: Extra::default(),
+    /// }));
+    /// let mut zipped = Cursor::new(Vec::new());
+    /// pack.write_zip(&mut zipped).unwrap();
+    /// let (loaded, errors) = DataPack::load_zip(zipped).unwrap();
+    /// assert!(errors.is_empty());
+    /// assert_eq!(loaded, pack);
+    /// ```
+    pub fn load_zip<R: Read + Seek>(reader: R) -> LoadResult {
+        let mut archive = ZipArchive::new(reader).map_err(|err| zip_error("", err))?;
+        let meta = match read_entry(&mut archive, "pack.mcmeta")? {
+            Some(meta) => parse("pack.mcmeta", &meta)?,
+            None => return Err(zip_error("pack.mcmeta", ZipError::FileNotFound)),
+        };
+        DataPack::load_archive_data(archive, meta)
+    }
+
+    /// Load the `data` folder inside a mod or game `.jar` file as a data pack. If the archive
+    /// has no `pack.mcmeta` at its root, `meta` is used instead; if it has an invalid one, it is
+    /// an error, as for [`DataPack::load_zip`].
+    ///
+    /// ```
+    /// # use std::io::{Cursor, Write};
+    /// # use zip::{ZipWriter, write::FileOptions};
+    /// # use minecraft_json::minecraft::data::pack::{DataPack, LoadErrorKind};
+    /// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+    /// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+    /// let meta = McMeta::Pack {
+    ///     description: TextComponent::RawTextLike(StringLike::String("fallback".to_string())),
+    ///     pack_format: pack_format::VER_7,
+    /// };
+    /// let mut jar = ZipWriter::new(Cursor::new(Vec::new()));
+    /// jar.start_file("pack.mcmeta", FileOptions::default()).unwrap();
+    /// jar.write_all(br#"{"pack": {"description": "broken"}}"#).unwrap();
+    /// let err = DataPack::load_jar(jar.finish().unwrap(), meta).unwrap_err();
+    /// assert_eq!(err.path, "pack.mcmeta");
+    /// assert!(matches!(err.error, LoadErrorKind::Json(_)));
+    /// ```
+    pub fn load_jar<R: Read + Seek>(reader: R, meta: McMeta) -> LoadResult {
+        let mut archive = ZipArchive::new(reader).map_err(|err| zip_error("", err))?;
+        let meta = match read_entry(&mut archive, "pack.mcmeta")? {
+            Some(own_meta) => parse("pack.mcmeta", &own_meta)?,
+            None => meta,
+        };
+        DataPack::load_archive_data(archive, meta)
+    }
+
+    fn load_archive_data<R: Read + Seek>(mut archive: ZipArchive<R>, meta: McMeta) -> LoadResult {
+        let mut pack = DataPack::new(meta);
+        let mut errors = Vec::new();
+        for k in 0..archive.len() {
+            let mut file = match archive.by_index(k) {
+                Ok(file) => file,
+                Err(err) => {
+                    errors.push(zip_error(&format!("#{}", k), err));
+                    continue;
+                }
+            };
+            let path = file.name().replace('\\', "/");
+            if file.is_dir() || !path.starts_with("data/") { continue; }
+            let mut contents = String::new();
+            match file.read_to_string(&mut contents) {
+                Ok(_) => pack.add_file(&path, &contents, &mut errors),
+                Err(err) => errors.push(LoadError { path, error: LoadErrorKind::Io(err) }),
+            }
+        }
+        Ok((pack, errors))
+    }
+
+    /// Write this data pack as a zip archive, with `pack.mcmeta` at its root. Resource IDs
+    /// that would give an unsafe entry name are rejected, as for [`DataPack::write`].
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zip::result::ZipError;
+    /// # use minecraft_json::minecraft::data::pack::DataPack;
+    /// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+    /// # use minecraft_json::minecraft::data::predicate::Predicate;
+    /// # use minecraft_json::minecraft::common::{Either, Extra};
+    /// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+    /// let mut pack = DataPack::new(McMeta::Pack {
+    ///     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+    ///     pack_format: pack_format::VER_7,
+    /// });
+    /// pack.predicates.insert("demo:../../escape".to_string(),
+    ///                        Either::Left(Predicate::KilledByPlayer { inverse: false, extra: Extra::default() }));
+    /// let err = pack.write_zip(Cursor::new(Vec::new())).unwrap_err();
+    /// assert!(matches!(err, ZipError::Io(err) if err.kind() == std::io::ErrorKind::InvalidInput));
+    /// ```
+    pub fn write_zip<W: Write + Seek>(&self, writer: W) -> ZipResult<()> {
+        let files = self.to_files().map_err(std::io::Error::from)?;
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(writer);
+        for (path, contents) in files {
+            check_path(&path)?;
+            zip.start_file(path, options)?;
+            zip.write_all(contents.as_bytes())?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<String>, LoadError> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(zip_error(name, err)),
+    };
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|err| LoadError { path: name.to_string(), error: LoadErrorKind::Io(err) })?;
+    Ok(Some(contents))
+}
+
+fn zip_error(path: &str, err: ZipError) -> LoadError {
+    LoadError { path: path.to_string(), error: LoadErrorKind::Zip(err) }
+}
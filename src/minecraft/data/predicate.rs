@@ -26,45 +26,111 @@
 
 use std::collections::BTreeMap;
 use derivative::Derivative;
-use serde::{Serialize, Deserialize};
-use serde_json::Number;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::de::Error;
+use serde::ser::{Error as _, SerializeMap};
+use serde_json::{Map, Number, Value};
+use crate::minecraft::data::extension::{self, CustomCondition};
 use crate::minecraft::data::conditions::{DamageSource, Entity, Location, Item};
-use crate::minecraft::common::{Ranged, Ranged2, NumberProviderValue};
+use crate::minecraft::common::{Ranged, Ranged2, NumberProviderValue, Either, ResourceLocation, Extra};
+use crate::minecraft::error;
 use crate::defaults;
 
 /// Predicate.
+///
+/// Unknown fields of vanilla conditions are kept in `extra`, see [`Extra`]:
+///
+/// ```
+/// # use minecraft_json::minecraft::data::predicate::Predicate;
+/// # use minecraft_json::minecraft::error;
+/// let json = r#"{"condition":"random_chance","chance":0.5,"bogus":1}"#;
+/// let predicate: Predicate = serde_json::from_str(json).unwrap();
+/// assert_eq!(serde_json::to_string(&predicate).unwrap(), json);
+///
+/// let err = error::strict(|| error::from_str::<Predicate>(json)).unwrap_err();
+/// assert_eq!(err.to_string(), "bogus: unknown field at line 1 column 51");
+/// ```
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Serialize, Deserialize)]
-#[serde(tag = "condition", rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "condition", rename_all = "snake_case", remote = "Self")]
 #[non_exhaustive]
 pub enum Predicate {
+    /// Joins conditions from parameter terms with "and". Replaces lists of predicates since
+    /// Minecraft 1.20.
+    #[serde(alias = "minecraft:all_of")]
+    AllOf {
+        /// A list of conditions to join using 'and'.
+        terms: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Joins conditions from parameter terms with "or". Replaces [`Predicate::Alternative`]
+    /// since Minecraft 1.20.
+    #[serde(alias = "minecraft:any_of")]
+    AnyOf {
+        /// A list of conditions to join using 'or'.
+        terms: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
     /// Joins conditions from parameter terms with "or".
+    #[serde(alias = "minecraft:alternative")]
     Alternative {
         /// A list of conditions to join using 'or'.
         terms: Vec<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Check properties of a block state.
+    #[serde(alias = "minecraft:block_state_property")]
     BlockStateProperty {
         /// A block ID. The test fails if the block doesn't match.
-        block: String,
+        block: ResourceLocation,
         /// A map of block property names to values. All values are strings. The test fails if
         /// the block doesn't match.
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
         properties: BTreeMap<String, String>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Check properties of damage source.
+    #[serde(alias = "minecraft:damage_source_properties")]
     DamageSourceProperties {
         /// Predicate applied to the damage source.
         predicate: DamageSource,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Check whether the enchantment is active, only usable in enchantment effects.
+    #[serde(alias = "minecraft:enchantment_active_check")]
+    EnchantmentActiveCheck {
+        /// Whether the enchantment should be active or inactive.
+        active: bool,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test properties of an entity.
+    #[serde(alias = "minecraft:entity_properties")]
     EntityProperties {
         /// Specifies the entity to check for the condition.
         entity: WhichEntity,
         /// Predicate applied to entity, uses same structure as advancements.
         predicate: Box<Entity>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test the scoreboard scores of an entity.
+    #[serde(alias = "minecraft:entity_scores")]
     EntityScores {
         /// Specifies the entity to check for the condition.
         entity: WhichEntity,
@@ -73,20 +139,32 @@ pub enum Predicate {
         /// Item: Key name is the objective while the value is the exact score value (or a range
         /// of score values) required for the condition to pass.
         scores: BTreeMap<String, Ranged<isize>>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Inverts condition from parameter term.
+    #[serde(alias = "minecraft:inverted")]
     Inverted {
         /// The condition to be negated.
         term: Box<Predicate>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test if a [`WhichEntity::KillerPlayer`] entity is available.
+    #[serde(alias = "minecraft:killed_by_player")]
     KilledByPlayer {
         /// If true, the condition passes if [`WhichEntity::KillerPlayer`] is *not* available.
         #[serde(default, skip_serializing_if = "defaults::is_default")]
         inverse: bool,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Checks if the current location matches.
     #[serde(rename_all = "camelCase")]
+    #[serde(alias = "minecraft:location_check")]
     LocationCheck {
         /// Optional offsets to location.
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -99,42 +177,94 @@ pub enum Predicate {
         offset_z: Option<isize>,
         /// Predicate applied to location, uses same structure as advancements.
         predicate: Box<Location>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Checks tool.
+    #[serde(alias = "minecraft:match_tool")]
     MatchTool {
         /// Predicate applied to item, uses same structure as advancements.
         predicate: Box<Item>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test if a random number 0.0–1.0 is less than a specified value.
+    #[serde(alias = "minecraft:random_chance")]
     RandomChance {
         /// Success rate as a number 0.0–1.0.
         chance: Number,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test if a random number 0.0–1.0 is less than a specified value, affected by the level of
     /// `Looting` on the killer entity.
+    #[serde(alias = "minecraft:random_chance_with_looting")]
     RandomChanceWithLooting {
         /// Base success rate.
         chance: Number,
         /// Looting adjustment to the base success rate.
         /// Formula is `chance + (looting_level * looting_multiplier)`.
         looting_multiplier: Number,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Test if a random number 0.0–1.0 is less than a specified value, depending on the level of
+    /// an enchantment on the attacker. Replaces [`Predicate::RandomChanceWithLooting`] since
+    /// Minecraft 1.21.
+    #[serde(alias = "minecraft:random_chance_with_enchanted_bonus")]
+    RandomChanceWithEnchantedBonus {
+        /// Success rate when the enchantment is absent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unenchanted_chance: Option<Number>,
+        /// Success rate depending on the enchantment level.
+        enchanted_chance: LevelBasedValue,
+        /// ID of the enchantment.
+        enchantment: ResourceLocation,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Test if another referred condition (predicate) passes.
+    #[serde(alias = "minecraft:reference")]
     Reference {
         /// The namespaced ID of the condition (predicate) referred to. A cyclic reference
         /// causes a parsing failure.
-        name: String,
+        name: ResourceLocation,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Returns true with 1/explosion radius probability.
-    SurvivesExplosion,
+    #[serde(alias = "minecraft:survives_explosion")]
+    SurvivesExplosion {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
     /// Passes with probability picked from table, indexed by enchantment level.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::predicate::Predicate;
+    /// let json = r#"{"condition":"minecraft:table_bonus","enchantment":"minecraft:fortune","chances":[0.05,0.0625]}"#;
+    /// let predicate: Predicate = serde_json::from_str(json).unwrap();
+    /// assert!(matches!(&predicate, Predicate::TableBonus { enchantment, .. } if enchantment.path() == "fortune"));
+    /// ```
+    #[serde(alias = "minecraft:table_bonus")]
     TableBonus {
-        /// Id of enchantment.
-        enchantment: isize,
+        /// ID of the enchantment.
+        enchantment: ResourceLocation,
         /// List of probabilities for enchantment level, indexed from 0.
         chances: Vec<Number>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Checks the current time.
+    #[serde(alias = "minecraft:time_check")]
     TimeCheck {
         /// The time value in ticks.
         value: Ranged2<isize, NumberProviderValue<isize>>,
@@ -142,8 +272,12 @@ pub enum Predicate {
         /// value operates on a time period of daytime ticks just like `/time query daytime`).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         period: Option<isize>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Checks for a current weather state.
+    #[serde(alias = "minecraft:weather_check")]
     WeatherCheck {
         /// If true, the condition evaluates to true only if it's raining or thundering.
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -151,19 +285,116 @@ pub enum Predicate {
         /// If true, the condition evaluates to true only if it's thundering.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         thundering: Option<bool>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
     },
     /// Checks for range of value.
+    #[serde(alias = "minecraft:value_check")]
     ValueCheck {
         /// Number Provider. The value to test.
         value: NumberProviderValue<isize>,
         /// The exact value to check, or the range to check the value.
         range: Ranged<NumberProviderValue<isize>>,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// A custom condition registered with [`register_condition`](extension::register_condition).
+    #[serde(skip)]
+    Custom {
+        /// The condition ID, as written.
+        condition: String,
+        /// The typed fields, except `condition`.
+        properties: Box<dyn CustomCondition>,
+    },
+    /// A condition unknown to this crate, e.g. from a newer version or a mod. All fields are
+    /// kept in their original order.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::predicate::Predicate;
+    /// let json = r#"{"condition":"mymod:moon_phase","phase":4,"exact":true}"#;
+    /// let predicate: Predicate = serde_json::from_str(json).unwrap();
+    /// assert!(matches!(&predicate, Predicate::Other { condition, .. } if condition == "mymod:moon_phase"));
+    /// assert_eq!(serde_json::to_string(&predicate).unwrap(), json);
+    /// ```
+    #[serde(skip)]
+    Other {
+        /// The condition ID, as written.
+        condition: String,
+        /// All fields except `condition`.
+        properties: Map<String, Value>,
     },
 }
 
+impl Serialize for Predicate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Predicate::Custom { condition, properties } => {
+                let properties = match properties.to_value().map_err(S::Error::custom)? {
+                    Value::Object(properties) => properties,
+                    _ => return Err(S::Error::custom("custom condition is not a JSON object")),
+                };
+                let mut map = serializer.serialize_map(Some(properties.len() + 1))?;
+                map.serialize_entry("condition", condition)?;
+                for (key, value) in &properties {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Predicate::Other { condition, properties } => {
+                let mut map = serializer.serialize_map(Some(properties.len() + 1))?;
+                map.serialize_entry("condition", condition)?;
+                for (key, value) in properties {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            _ => Predicate::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Predicate {
+    /// Same as the derived implementation, except that conditions registered in [`extension`]
+    /// are parsed as [`Predicate::Custom`], and unknown conditions as [`Predicate::Other`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut properties = Map::deserialize(deserializer)?;
+        if let Some(Value::String(condition)) = properties.get("condition") {
+            let condition = condition.clone();
+            if let Some(parse) = extension::condition(&condition) {
+                properties.shift_remove("condition");
+                let properties = parse(Value::Object(properties)).map_err(D::Error::custom)?;
+                return Ok(Predicate::Custom { condition, properties });
+            }
+            if !Predicate::VANILLA_CONDITIONS.contains(&ResourceLocation::normalise(&condition).as_str()) {
+                properties.shift_remove("condition");
+                return Ok(Predicate::Other { condition, properties });
+            }
+        }
+        error::nested_value(Value::Object(properties), |d| Predicate::deserialize(d))
+    }
+}
+
+impl Predicate {
+    /// IDs of all vanilla conditions, i.e. those not parsed as [`Predicate::Custom`] or
+    /// [`Predicate::Other`].
+    pub const VANILLA_CONDITIONS: &'static [&'static str] = &[
+        "minecraft:all_of", "minecraft:any_of", "minecraft:alternative",
+        "minecraft:block_state_property", "minecraft:damage_source_properties",
+        "minecraft:enchantment_active_check", "minecraft:entity_properties",
+        "minecraft:entity_scores", "minecraft:inverted", "minecraft:killed_by_player",
+        "minecraft:location_check", "minecraft:match_tool", "minecraft:random_chance",
+        "minecraft:random_chance_with_looting", "minecraft:random_chance_with_enchanted_bonus",
+        "minecraft:reference", "minecraft:survives_explosion", "minecraft:table_bonus",
+        "minecraft:time_check", "minecraft:weather_check", "minecraft:value_check",
+    ];
+}
+
 /// Specifies the entity to check for the condition.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Derivative, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WhichEntity {
     /// The entity that died or the player that gained the advancement.
@@ -172,4 +403,66 @@ pub enum WhichEntity {
     Killer,
     /// A killer that is a player.
     KillerPlayer,
+    /// The entity that directly killed, e.g. the arrow rather than the skeleton.
+    DirectKiller,
+    /// The attacker, in enchantment effects since Minecraft 1.21.
+    Attacker,
+    /// The entity that directly attacked, in enchantment effects since Minecraft 1.21.
+    DirectAttacker,
+    /// An attacker that is a player, in enchantment effects since Minecraft 1.21.
+    AttackingPlayer,
+}
+
+/// A value depending on an enchantment level, either a constant or a [`LevelBasedProvider`].
+pub type LevelBasedValue = Either<Number, Box<LevelBasedProvider>>;
+
+/// Computes a value from an enchantment level.
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type")]
+pub enum LevelBasedProvider {
+    /// Increases linearly with the level.
+    #[serde(rename = "minecraft:linear", alias = "linear")]
+    Linear {
+        /// Value at level 1.
+        base: Number,
+        /// Added for each level above level 1.
+        per_level_above_first: Number,
+    },
+    /// Clamps another value within bounds.
+    #[serde(rename = "minecraft:clamped", alias = "clamped")]
+    Clamped {
+        /// The value to clamp.
+        value: LevelBasedValue,
+        /// The lower bound.
+        min: Number,
+        /// The upper bound.
+        max: Number,
+    },
+    /// Divides two values.
+    #[serde(rename = "minecraft:fraction", alias = "fraction")]
+    Fraction {
+        /// The numerator.
+        numerator: LevelBasedValue,
+        /// The denominator.
+        denominator: LevelBasedValue,
+    },
+    /// The level squared, plus a constant.
+    #[serde(rename = "minecraft:levels_squared", alias = "levels_squared")]
+    LevelsSquared {
+        /// Added to the squared level.
+        added: Number,
+    },
+    /// Looks up the value by level in a list.
+    #[serde(rename = "minecraft:lookup", alias = "lookup")]
+    Lookup {
+        /// Values for levels starting from 1.
+        values: Vec<Number>,
+        /// The value for levels beyond the list.
+        fallback: LevelBasedValue,
+    },
 }
+
+/// A predicate JSON file, either a single predicate or a list of predicates that must all pass.
+pub type PredicateFile = Either<Predicate, Vec<Predicate>>;
@@ -0,0 +1,398 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Recipes are used to define the way players craft, smelt, or otherwise create items. They are
+//! a part of data packs.
+//!
+//! Inside a data pack, a recipe is located within `data/<namespace>/recipes`.
+//!
+//! ```
+//! # use maplit::btreemap;
+//! # use minecraft_json::assert_equiv_pretty;
+//! # use minecraft_json::minecraft::data::recipe::{Recipe, Ingredient, RecipeResult};
+//! # use minecraft_json::minecraft::common::{Either, Extra};
+//! assert_equiv_pretty!(r###"{
+//!   "type": "minecraft:crafting_shaped",
+//!   "pattern": [
+//!     "##",
+//!     "##"
+//!   ],
+//!   "key": {
+//!     "#": {
+//!       "tag": "minecraft:planks"
+//!     }
+//!   },
+//!   "result": {
+//!     "item": "minecraft:crafting_table"
+//!   }
+//! }"###, Recipe::CraftingShaped {
+//!     group: None,
+//!     pattern: vec!["##".to_string(), "##".to_string()],
+//!     key: btreemap! {
+//!         "#".to_string() => Either::Left(Ingredient::Tag {
+//!             tag: "minecraft:planks".parse().unwrap(),
+//!         }),
+//!     },
+//!     result: RecipeResult {
+//!         item: "minecraft:crafting_table".parse().unwrap(),
+//!         count: None,
+//!         extra: Extra::default(),
+//!     },
+//!     extra: Extra::default(),
+//! });
+//! ```
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize, Deserializer};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::Number;
+use crate::minecraft::common::{Either, ResourceLocation, Extra};
+use crate::minecraft::error;
+
+/// A recipe JSON file.
+///
+/// Unknown fields are kept in `extra`, see [`Extra`]:
+///
+/// ```
+/// # use minecraft_json::minecraft::data::recipe::Recipe;
+/// # use minecraft_json::minecraft::error;
+/// for json in [
+///     r#"{"type":"crafting_shaped","pattern":["X"],"key":{"X":{"item":"a"}},"result":{"item":"b"},"bogus":1}"#,
+///     r#"{"type":"crafting_shapeless","ingredients":[{"item":"a"}],"result":{"item":"b"},"bogus":1}"#,
+///     r#"{"type":"stonecutting","ingredient":{"item":"a"},"result":"b","count":2,"bogus":1}"#,
+///     r#"{"type":"smithing","base":{"item":"a"},"addition":{"item":"b"},"result":{"item":"c"},"bogus":1}"#,
+///     r#"{"type":"crafting_special_armordye","bogus":1}"#,
+/// ] {
+///     let recipe: Recipe = serde_json::from_str(json).unwrap();
+///     assert!(serde_json::to_string(&recipe).unwrap().ends_with(r#","bogus":1}"#));
+///     let err = error::strict(|| error::from_str::<Recipe>(json)).unwrap_err();
+///     assert_eq!((err.path.as_str(), err.message.as_str()), ("bogus", "unknown field"));
+/// }
+/// ```
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type")]
+#[non_exhaustive]
+pub enum Recipe {
+    /// Represents a shaped crafting recipe in a crafting table.
+    ///
+    /// The key used in the pattern may be any single character except the space character,
+    /// which is reserved for empty slots in a recipe.
+    #[serde(rename = "minecraft:crafting_shaped", alias = "crafting_shaped")]
+    CraftingShaped {
+        /// Optional. A string identifier. Used to group multiple recipes together in the recipe
+        /// book.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
+        /// A list of single-character keys used to describe a pattern for shaped crafting. Each
+        /// row in the crafting grid is one string in this list containing 3 or less keys. All
+        /// strings in this list need to have the same amount of keys. A space can be used to
+        /// indicate an empty spot.
+        pattern: Vec<String>,
+        /// All keys used for this shaped crafting recipe.
+        ///
+        /// Item: The ingredient corresponding to this key.
+        key: BTreeMap<String, IngredientChoice>,
+        /// The output item of the recipe.
+        result: RecipeResult,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Represents a shapeless crafting recipe in a crafting table.
+    #[serde(rename = "minecraft:crafting_shapeless", alias = "crafting_shapeless")]
+    CraftingShapeless {
+        /// Optional. A string identifier. Used to group multiple recipes together in the recipe
+        /// book.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
+        /// A list of entries for this shapeless crafting recipe. Must have 1 to 9 entries.
+        ingredients: Vec<IngredientChoice>,
+        /// The output item of the recipe.
+        result: RecipeResult,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Represents a recipe in a furnace.
+    #[serde(rename = "minecraft:smelting", alias = "smelting")]
+    Smelting(CookingRecipe),
+    /// Represents a recipe in a blast furnace.
+    #[serde(rename = "minecraft:blasting", alias = "blasting")]
+    Blasting(CookingRecipe),
+    /// Represents a recipe in a smoker.
+    #[serde(rename = "minecraft:smoking", alias = "smoking")]
+    Smoking(CookingRecipe),
+    /// Represents a recipe in a campfire.
+    #[serde(rename = "minecraft:campfire_cooking", alias = "campfire_cooking")]
+    CampfireCooking(CookingRecipe),
+    /// Represents a recipe in a stonecutter.
+    #[serde(rename = "minecraft:stonecutting", alias = "stonecutting")]
+    Stonecutting {
+        /// Optional. A string identifier. Used to group multiple recipes together in the recipe
+        /// book.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
+        /// The ingredient for the recipe.
+        ingredient: IngredientChoice,
+        /// An item ID. The output item of the recipe.
+        result: ResourceLocation,
+        /// The amount of the output item.
+        count: isize,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Represents an upgrade recipe in a smithing table.
+    #[serde(rename = "minecraft:smithing", alias = "smithing")]
+    Smithing {
+        /// Optional. A string identifier. Used to group multiple recipes together in the recipe
+        /// book.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
+        /// Ingredient specifying an item to be upgraded.
+        base: Ingredient,
+        /// The ingredient to be added to the base item.
+        addition: Ingredient,
+        /// Item specifying the resulting type of the upgraded item. The count is ignored.
+        result: RecipeResult,
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Dyeing leather armor.
+    #[serde(rename = "minecraft:crafting_special_armordye", alias = "crafting_special_armordye")]
+    ArmorDye {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Copying patterns from one banner to another.
+    #[serde(rename = "minecraft:crafting_special_bannerduplicate",
+    alias = "crafting_special_bannerduplicate")]
+    BannerDuplicate {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Copying written books.
+    #[serde(rename = "minecraft:crafting_special_bookcloning", alias = "crafting_special_bookcloning")]
+    BookCloning {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Crafting firework rockets.
+    #[serde(rename = "minecraft:crafting_special_firework_rocket",
+    alias = "crafting_special_firework_rocket")]
+    FireworkRocket {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Crafting firework stars.
+    #[serde(rename = "minecraft:crafting_special_firework_star",
+    alias = "crafting_special_firework_star")]
+    FireworkStar {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Adding a fade effect to firework stars.
+    #[serde(rename = "minecraft:crafting_special_firework_star_fade",
+    alias = "crafting_special_firework_star_fade")]
+    FireworkStarFade {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Copying maps.
+    #[serde(rename = "minecraft:crafting_special_mapcloning", alias = "crafting_special_mapcloning")]
+    MapCloning {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Extending (zooming out) maps.
+    #[serde(rename = "minecraft:crafting_special_mapextending",
+    alias = "crafting_special_mapextending")]
+    MapExtending {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Repairing items by combining two damaged items of the same kind.
+    #[serde(rename = "minecraft:crafting_special_repairitem", alias = "crafting_special_repairitem")]
+    RepairItem {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Applying a banner's pattern to a shield.
+    #[serde(rename = "minecraft:crafting_special_shielddecoration",
+    alias = "crafting_special_shielddecoration")]
+    ShieldDecoration {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Dyeing a shulker box while keeping its contents.
+    #[serde(rename = "minecraft:crafting_special_shulkerboxcoloring",
+    alias = "crafting_special_shulkerboxcoloring")]
+    ShulkerBoxColoring {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Crafting tipped arrows from lingering potions.
+    #[serde(rename = "minecraft:crafting_special_tippedarrow", alias = "crafting_special_tippedarrow")]
+    TippedArrow {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+    /// Crafting suspicious stew from flowers.
+    #[serde(rename = "minecraft:crafting_special_suspiciousstew",
+    alias = "crafting_special_suspiciousstew")]
+    SuspiciousStew {
+        /// Fields not known to this crate, see [`Extra`].
+        #[serde(flatten)]
+        extra: Extra,
+    },
+}
+
+/// Common fields for cooking recipes: [`Recipe::Smelting`], [`Recipe::Blasting`],
+/// [`Recipe::Smoking`] and [`Recipe::CampfireCooking`].
+///
+/// ```
+/// # use minecraft_json::assert_equiv_pretty_protected;
+/// # use minecraft_json::minecraft::data::recipe::{Recipe, CookingRecipe, Ingredient};
+/// # use minecraft_json::minecraft::common::{Either, Extra};
+/// assert_equiv_pretty_protected!(r#"{
+///   "type": "minecraft:smelting",
+///   "ingredient": [
+///     {
+///       "item": "minecraft:iron_ore"
+///     },
+///     {
+///       "item": "minecraft:deepslate_iron_ore"
+///     }
+///   ],
+///   "result": "minecraft:iron_ingot",
+///   "experience": 0.7,
+///   "cookingtime": 200
+/// }"#, Recipe::Smelting(CookingRecipe {
+///     group: None,
+///     ingredient: Either::Right(vec![
+///         Ingredient::Item { item: "minecraft:iron_ore".parse().unwrap() },
+///         Ingredient::Item { item: "minecraft:deepslate_iron_ore".parse().unwrap() },
+///     ]),
+///     result: "minecraft:iron_ingot".parse().unwrap(),
+///     experience: "0.7".parse().unwrap(),
+///     cooking_time: Some(200),
+///     extra: Extra::default(),
+/// }));
+/// ```
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct CookingRecipe {
+    /// Optional. A string identifier. Used to group multiple recipes together in the recipe book.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// The ingredient for the recipe.
+    pub ingredient: IngredientChoice,
+    /// An item ID. The output item of the recipe.
+    pub result: ResourceLocation,
+    /// The output experience of the recipe.
+    pub experience: Number,
+    /// Optional. The cook time of the recipe in ticks. Defaults to 200 for smelting, 100 for
+    /// blasting and smoking, and 100 for campfire cooking.
+    #[serde(default, rename = "cookingtime", skip_serializing_if = "Option::is_none")]
+    pub cooking_time: Option<isize>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// An acceptable ingredient: either a single item ID or an item tag.
+///
+/// ```
+/// # use minecraft_json::minecraft::{data::recipe::Recipe, error};
+/// let json = r#"{
+///   "type": "minecraft:crafting_shapeless",
+///   "ingredients": [{"tag": "minecraft:planks"}, {"item": "minecraft:Stick"}],
+///   "result": {"item": "minecraft:stone"}
+/// }"#;
+/// let err = error::from_str::<Recipe>(json).unwrap_err();
+/// assert_eq!(err.path, "ingredients[1].item");
+/// assert_eq!(err.line, 3);
+/// ```
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum Ingredient {
+    /// An item ID.
+    Item {
+        #[allow(missing_docs)]
+        item: ResourceLocation,
+    },
+    /// An item tag.
+    Tag {
+        #[allow(missing_docs)]
+        tag: ResourceLocation,
+    },
+}
+
+impl<'de> Deserialize<'de> for Ingredient {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Item {
+            item: ResourceLocation,
+        }
+        #[derive(Deserialize)]
+        struct Tag {
+            tag: ResourceLocation,
+        }
+        error::untagged(deserializer, "Ingredient", &[
+            &|v| error::variant(v, |Item { item }| Ingredient::Item { item }),
+            &|v| error::variant(v, |Tag { tag }| Ingredient::Tag { tag }),
+        ])
+    }
+}
+
+/// Either a single [`Ingredient`], or a list of acceptable ingredients.
+pub type IngredientChoice = Either<Ingredient, Vec<Ingredient>>;
+
+/// The output item of a crafting or smithing recipe.
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct RecipeResult {
+    /// An item ID.
+    pub item: ResourceLocation,
+    /// Optional. The amount of the item. Defaults to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<isize>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
@@ -0,0 +1,631 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Vanilla registry entries (blocks, items, biomes, enchantments, etc.), for validating IDs in
+//! conditions and predicates offline.
+//!
+//! Small registries are embedded for Minecraft 1.16.2–1.17.1 only, see [`Registries::vanilla`].
+//! All registries for any version, including blocks with their state properties, can be loaded
+//! from the `reports` directory produced by the data generator
+//! (`java -cp server.jar net.minecraft.data.Main --reports`), see [`Registries::load_reports`].
+//! Registries without any entry are never checked.
+//!
+//! Block states and fluid states in conditions are checked against block and fluid definitions,
+//! see [`Registries::check_predicate_states`]. Blocks without a definition are never checked.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::conditions::{Entity, Location};
+//! # use minecraft_json::minecraft::data::meta::pack_format;
+//! # use minecraft_json::minecraft::data::registry::{Registries, RegistryKind, StateIssueKind};
+//! let registries = Registries::vanilla(pack_format::VER_7).unwrap();
+//! let entity: Entity = serde_json::from_str(r#"{
+//!   "effects": {"minecraft:speed": {}, "minecraft:sped": {}},
+//!   "location": {"dimension": "the_nether", "biome": "minecraft:no_such_biome"}
+//! }"#).unwrap();
+//! let unknown = registries.check_entity(&entity);
+//! assert_eq!(unknown.len(), 1);
+//! assert_eq!(unknown[0].kind, RegistryKind::MobEffect);
+//! assert_eq!(unknown[0].to_string(), "effects.minecraft:sped: unknown mob effect 'minecraft:sped'");
+//!
+//! let reports = r#"{"minecraft:worldgen/biome": {"entries": {"minecraft:plains": {}}}}"#;
+//! let registries = Registries::from_reports(reports, None).unwrap();
+//! assert_eq!(registries.check_entity(&entity)[0].location, "location.biome");
+//!
+//! let blocks = r#"{"minecraft:furnace": {"properties": {
+//!   "facing": ["north", "south", "west", "east"], "lit": ["true", "false"]
+//! }}}"#;
+//! let registries = Registries::from_reports("{}", Some(blocks)).unwrap();
+//! let location: Location = serde_json::from_str(r#"{"block": {
+//!   "blocks": ["minecraft:furnace"],
+//!   "state": {"facing": "up", "lit": true, "level": 3}
+//! }}"#).unwrap();
+//! let issues = registries.check_location_states(&location);
+//! assert_eq!(issues.len(), 2);
+//! assert_eq!(issues[0].to_string(),
+//!            "block.state.facing: invalid value 'up' for property 'facing' of 'minecraft:furnace'");
+//! assert_eq!(issues[1].kind, StateIssueKind::UnknownProperty);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+use crate::minecraft::common::{Either, PlainValue, Ranged, ResourceLocation, TagOrId, TagOrIds};
+use crate::minecraft::data::conditions::{
+    Block, DamageSource, Entity, Fluid, Item, LightningBolt, Location, Player, TypeSpecific,
+};
+use crate::minecraft::data::meta::pack_format;
+use crate::minecraft::data::predicate::Predicate;
+use crate::minecraft::span::join;
+
+mod vanilla;
+
+/// Kinds of registries relevant to data packs.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Hash)]
+pub enum RegistryKind {
+    /// Blocks.
+    Block,
+    /// Items.
+    Item,
+    /// Entity types.
+    EntityType,
+    /// Biomes.
+    Biome,
+    /// Dimensions.
+    Dimension,
+    /// Status effects.
+    MobEffect,
+    /// Enchantments.
+    Enchantment,
+    /// Potions.
+    Potion,
+    /// Structures, also known as structure features.
+    Structure,
+    /// Statistic types.
+    StatType,
+}
+
+impl RegistryKind {
+    /// All kinds of registries.
+    pub const ALL: [RegistryKind; 10] = [
+        RegistryKind::Block, RegistryKind::Item, RegistryKind::EntityType, RegistryKind::Biome,
+        RegistryKind::Dimension, RegistryKind::MobEffect, RegistryKind::Enchantment,
+        RegistryKind::Potion, RegistryKind::Structure, RegistryKind::StatType,
+    ];
+
+    /// Names of this registry in `reports/registries.json`, across versions.
+    pub fn report_names(self) -> &'static [&'static str] {
+        match self {
+            RegistryKind::Block => &["minecraft:block"],
+            RegistryKind::Item => &["minecraft:item"],
+            RegistryKind::EntityType => &["minecraft:entity_type"],
+            RegistryKind::Biome => &["minecraft:worldgen/biome"],
+            RegistryKind::Dimension => &["minecraft:dimension"],
+            RegistryKind::MobEffect => &["minecraft:mob_effect"],
+            RegistryKind::Enchantment => &["minecraft:enchantment"],
+            RegistryKind::Potion => &["minecraft:potion"],
+            RegistryKind::Structure =>
+                &["minecraft:worldgen/structure_feature", "minecraft:worldgen/structure"],
+            RegistryKind::StatType => &["minecraft:stat_type"],
+        }
+    }
+}
+
+impl Display for RegistryKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            RegistryKind::Block => "block",
+            RegistryKind::Item => "item",
+            RegistryKind::EntityType => "entity type",
+            RegistryKind::Biome => "biome",
+            RegistryKind::Dimension => "dimension",
+            RegistryKind::MobEffect => "mob effect",
+            RegistryKind::Enchantment => "enchantment",
+            RegistryKind::Potion => "potion",
+            RegistryKind::Structure => "structure",
+            RegistryKind::StatType => "statistic type",
+        })
+    }
+}
+
+/// Definition of a block, as in `reports/blocks.json`. Also used for fluids.
+#[derive(Eq, PartialEq, Debug, Default, Clone)]
+#[derive(Deserialize)]
+pub struct BlockDefinition {
+    /// Block state properties, each with its allowed values.
+    #[serde(default)]
+    pub properties: BTreeMap<String, Vec<String>>,
+}
+
+impl BlockDefinition {
+    /// Whether or not all values of a property are integers. `None` if there is no such property.
+    pub fn is_integer(&self, property: &str) -> Option<bool> {
+        let values = self.properties.get(property)?;
+        Some(values.iter().all(|v| v.parse::<i64>().is_ok()))
+    }
+
+    /// Check a property against this definition, as in a block state or fluid state condition.
+    pub fn check_property(&self, property: &str, value: &PlainValue) -> Result<(), StateIssueKind> {
+        let values = self.properties.get(property).ok_or(StateIssueKind::UnknownProperty)?;
+        let value = match value {
+            PlainValue::Boolean(b) => b.to_string(),
+            PlainValue::Integer(Ranged::Exact(n)) => n.to_string(),
+            PlainValue::Integer(Ranged::Range { .. }) => return match self.is_integer(property) {
+                Some(true) => Ok(()),
+                _ => Err(StateIssueKind::NotInteger),
+            },
+            PlainValue::String(s) => s.clone(),
+        };
+        if values.contains(&value) { Ok(()) } else { Err(StateIssueKind::InvalidValue(value)) }
+    }
+}
+
+/// Kinds of problems in block state or fluid state conditions.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum StateIssueKind {
+    /// The block or fluid has no such property, so the condition never matches.
+    UnknownProperty,
+    /// The value is not allowed for the property, so the condition never matches.
+    InvalidValue(String),
+    /// A range of integers is used for a property whose values are not all integers.
+    NotInteger,
+}
+
+/// A problem in a block state or fluid state condition.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct StateIssue {
+    /// Location of the property, as a dot-separated path of JSON keys.
+    pub location: String,
+    /// The block or fluid whose definition the property is checked against.
+    pub owner: ResourceLocation,
+    /// Name of the property.
+    pub property: String,
+    /// What is wrong with the property.
+    pub kind: StateIssueKind,
+}
+
+impl Display for StateIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self.kind {
+            StateIssueKind::UnknownProperty =>
+                write!(f, "{}: '{}' has no property '{}'", self.location, self.owner, self.property),
+            StateIssueKind::InvalidValue(value) =>
+                write!(f, "{}: invalid value '{}' for property '{}' of '{}'",
+                       self.location, value, self.property, self.owner),
+            StateIssueKind::NotInteger =>
+                write!(f, "{}: property '{}' of '{}' is not an integer property",
+                       self.location, self.property, self.owner),
+        }
+    }
+}
+
+impl std::error::Error for StateIssue {}
+
+/// An ID not present in its registry.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct UnknownEntry {
+    /// Location of the ID, as a dot-separated path of JSON keys, with array indices in brackets.
+    pub location: String,
+    /// The registry that should contain the ID.
+    pub kind: RegistryKind,
+    /// The unknown ID.
+    pub id: ResourceLocation,
+}
+
+impl Display for UnknownEntry {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}: unknown {} '{}'", self.location, self.kind, self.id)
+    }
+}
+
+impl std::error::Error for UnknownEntry {}
+
+/// Entries of vanilla registries.
+#[derive(Eq, PartialEq, Debug, Default, Clone)]
+pub struct Registries {
+    entries: BTreeMap<RegistryKind, BTreeSet<ResourceLocation>>,
+    blocks: BTreeMap<ResourceLocation, BlockDefinition>,
+    fluids: BTreeMap<ResourceLocation, BlockDefinition>,
+}
+
+impl Registries {
+    /// Empty registries, which accept any ID.
+    pub fn new() -> Registries { Registries::default() }
+
+    /// Versions with embedded registries, see [`Registries::vanilla`].
+    pub const VANILLA_PACK_FORMATS: &'static [i64] = &[pack_format::VER_6, pack_format::VER_7];
+
+    /// Embedded registries for a [`pack_format`] in [`VANILLA_PACK_FORMATS`], i.e. Minecraft
+    /// 1.16.2–1.17.1, and `None` for any other version. Only dimensions, mob effects,
+    /// enchantments, potions, structures, statistic types and fluid definitions are embedded;
+    /// blocks (with their state properties), items, entity types and biomes are not, and neither
+    /// are newer versions. Embedding those, generated from the reports of each version up to
+    /// [`pack_format::VER_71`], is left to a separate change; until then, use
+    /// [`Registries::load_reports`] for them.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::meta::pack_format;
+    /// # use minecraft_json::minecraft::data::registry::{Registries, RegistryKind};
+    /// let registries = Registries::vanilla(pack_format::VER_6).unwrap();
+    /// assert!(registries.is_known(RegistryKind::Enchantment));
+    /// assert!(!registries.is_known(RegistryKind::Block));
+    /// assert!(Registries::vanilla(pack_format::VER_8).is_none());
+    /// ```
+    ///
+    /// [`VANILLA_PACK_FORMATS`]: Registries::VANILLA_PACK_FORMATS
+    pub fn vanilla(pack_format: i64) -> Option<Registries> {
+        if !Registries::VANILLA_PACK_FORMATS.contains(&pack_format) {
+            return None;
+        }
+        let (lists, fluids) = (vanilla::V1_16_2, vanilla::FLUIDS_1_16_2);
+        let vanilla = |id| ResourceLocation::new("minecraft", id).expect("embedded IDs are valid");
+        let mut registries = Registries::new();
+        for (kind, ids) in lists {
+            registries.insert(*kind, ids.iter().copied().map(vanilla));
+        }
+        for (id, properties) in fluids {
+            let properties = properties.iter().map(|(name, values)| {
+                (name.to_string(), values.iter().map(|v| v.to_string()).collect())
+            }).collect();
+            registries.insert_fluid(vanilla(id), BlockDefinition { properties });
+        }
+        Some(registries)
+    }
+
+    /// Load registries from the contents of `reports/registries.json` and optionally
+    /// `reports/blocks.json`.
+    pub fn from_reports(registries: &str, blocks: Option<&str>) -> serde_json::Result<Registries> {
+        #[derive(Deserialize)]
+        struct Report {
+            entries: BTreeMap<ResourceLocation, IgnoredAny>,
+        }
+        let reports: BTreeMap<String, Report> = serde_json::from_str(registries)?;
+        let mut result = Registries::new();
+        for &kind in RegistryKind::ALL.iter() {
+            for name in kind.report_names() {
+                if let Some(report) = reports.get(*name) {
+                    result.insert(kind, report.entries.keys().cloned());
+                }
+            }
+        }
+        if let Some(blocks) = blocks {
+            let blocks: BTreeMap<ResourceLocation, BlockDefinition> = serde_json::from_str(blocks)?;
+            result.insert(RegistryKind::Block, blocks.keys().cloned());
+            result.blocks.extend(blocks);
+        }
+        Ok(result)
+    }
+
+    /// Load registries from a `reports` directory of the data generator, which contains
+    /// `registries.json` and optionally `blocks.json`.
+    pub fn load_reports(dir: impl AsRef<Path>) -> io::Result<Registries> {
+        let dir = dir.as_ref();
+        let registries = fs::read_to_string(dir.join("registries.json"))?;
+        let blocks = match fs::read_to_string(dir.join("blocks.json")) {
+            Ok(blocks) => Some(blocks),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+        Registries::from_reports(&registries, blocks.as_deref())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Add entries to a registry.
+    pub fn insert(&mut self, kind: RegistryKind, ids: impl IntoIterator<Item=ResourceLocation>) {
+        self.entries.entry(kind).or_default().extend(ids);
+    }
+
+    /// Add a block definition, also adding the block to the block registry.
+    pub fn insert_block(&mut self, id: ResourceLocation, definition: BlockDefinition) {
+        self.insert(RegistryKind::Block, std::iter::once(id.clone()));
+        self.blocks.insert(id, definition);
+    }
+
+    /// Add a fluid definition. Fluids are not a registry in the data generator reports, so this is
+    /// the only way to check fluid state conditions.
+    pub fn insert_fluid(&mut self, id: ResourceLocation, definition: BlockDefinition) {
+        self.fluids.insert(id, definition);
+    }
+
+    /// Whether or not a registry has any entries, i.e. whether it is checked at all.
+    pub fn is_known(&self, kind: RegistryKind) -> bool {
+        self.entries.get(&kind).is_some_and(|ids| !ids.is_empty())
+    }
+
+    /// Whether or not a registry contains an ID.
+    pub fn contains(&self, kind: RegistryKind, id: &ResourceLocation) -> bool {
+        self.entries.get(&kind).is_some_and(|ids| ids.contains(id))
+    }
+
+    /// All entries of a registry.
+    pub fn entries(&self, kind: RegistryKind) -> impl Iterator<Item=&ResourceLocation> {
+        self.entries.get(&kind).into_iter().flatten()
+    }
+
+    /// The definition of a block, if loaded from `reports/blocks.json`.
+    pub fn block(&self, id: &ResourceLocation) -> Option<&BlockDefinition> {
+        self.blocks.get(id)
+    }
+
+    /// The definition of a fluid, if known.
+    pub fn fluid(&self, id: &ResourceLocation) -> Option<&BlockDefinition> {
+        self.fluids.get(id)
+    }
+
+    /// Report unknown IDs in a [`Location`] condition.
+    pub fn check_location(&self, location: &Location) -> Vec<UnknownEntry> {
+        self.walk(|w| w.location(location, String::new())).unknown
+    }
+
+    /// Report unknown IDs in an [`Entity`] condition.
+    pub fn check_entity(&self, entity: &Entity) -> Vec<UnknownEntry> {
+        self.walk(|w| w.entity(entity, String::new())).unknown
+    }
+
+    /// Report unknown IDs in an [`Item`] condition.
+    pub fn check_item(&self, item: &Item) -> Vec<UnknownEntry> {
+        self.walk(|w| w.item(item, String::new())).unknown
+    }
+
+    /// Report unknown IDs in a [`Predicate`], including nested predicates.
+    pub fn check_predicate(&self, predicate: &Predicate) -> Vec<UnknownEntry> {
+        self.walk(|w| w.predicate(predicate, String::new())).unknown
+    }
+
+    /// Report problems in the state of a [`Block`] condition, checked against each listed block.
+    pub fn check_block_state(&self, block: &Block) -> Vec<StateIssue> {
+        self.walk(|w| w.block(block, String::new())).states
+    }
+
+    /// Report problems in the state of a [`Fluid`] condition.
+    pub fn check_fluid_state(&self, fluid: &Fluid) -> Vec<StateIssue> {
+        self.walk(|w| w.fluid(fluid, String::new())).states
+    }
+
+    /// Report problems in block states and fluid states in a [`Location`] condition.
+    pub fn check_location_states(&self, location: &Location) -> Vec<StateIssue> {
+        self.walk(|w| w.location(location, String::new())).states
+    }
+
+    /// Report problems in block states and fluid states in an [`Entity`] condition.
+    pub fn check_entity_states(&self, entity: &Entity) -> Vec<StateIssue> {
+        self.walk(|w| w.entity(entity, String::new())).states
+    }
+
+    /// Report problems in block states and fluid states in a [`Predicate`], including
+    /// [`Predicate::BlockStateProperty`] and nested predicates.
+    pub fn check_predicate_states(&self, predicate: &Predicate) -> Vec<StateIssue> {
+        self.walk(|w| w.predicate(predicate, String::new())).states
+    }
+
+    fn walk(&self, f: impl FnOnce(&mut Walker)) -> Walker<'_> {
+        let mut walker = Walker { registries: self, unknown: Vec::new(), states: Vec::new() };
+        f(&mut walker);
+        walker
+    }
+}
+
+struct Walker<'a> {
+    registries: &'a Registries,
+    unknown: Vec<UnknownEntry>,
+    states: Vec<StateIssue>,
+}
+
+impl Walker<'_> {
+    fn check(&mut self, kind: RegistryKind, id: &ResourceLocation, location: String) {
+        if self.registries.is_known(kind) && !self.registries.contains(kind, id) {
+            self.unknown.push(UnknownEntry { location, kind, id: id.clone() });
+        }
+    }
+
+    /// Check the IDs (but not the tag) in `ids`.
+    fn ids(&mut self, kind: RegistryKind, ids: &TagOrIds, loc: String) {
+        match ids {
+            Either::Left(TagOrId::Id(id)) => self.check(kind, id, loc),
+            Either::Left(TagOrId::Tag(_)) => {}
+            Either::Right(ids) => for (k, id) in ids.iter().enumerate() {
+                self.check(kind, id, format!("{}[{}]", loc, k));
+            },
+        }
+    }
+
+    fn state(&mut self, definition: &BlockDefinition, owner: &ResourceLocation,
+             state: &BTreeMap<String, PlainValue>, loc: &str) {
+        for (property, value) in state {
+            if let Err(kind) = definition.check_property(property, value) {
+                self.states.push(StateIssue {
+                    location: join(loc, property),
+                    owner: owner.clone(),
+                    property: property.clone(),
+                    kind,
+                });
+            }
+        }
+    }
+
+    fn location(&mut self, location: &Location, loc: String) {
+        if let Some(biome) = &location.biome {
+            self.check(RegistryKind::Biome, biome, join(&loc, "biome"));
+        }
+        if let Some(dimension) = &location.dimension {
+            self.check(RegistryKind::Dimension, dimension, join(&loc, "dimension"));
+        }
+        if let Some(Ok(feature)) = location.feature.as_deref().map(str::parse) {
+            self.check(RegistryKind::Structure, &feature, join(&loc, "feature"));
+        }
+        if let Some(block) = &location.block {
+            self.block(block, join(&loc, "block"));
+        }
+        if let Some(fluid) = &location.fluid {
+            self.fluid(fluid, join(&loc, "fluid"));
+        }
+    }
+
+    fn block(&mut self, block: &Block, loc: String) {
+        for (k, id) in block.blocks.iter().enumerate() {
+            self.check(RegistryKind::Block, id, format!("{}[{}]", join(&loc, "blocks"), k));
+            if let Some(definition) = self.registries.block(id) {
+                self.state(definition, id, &block.state, &join(&loc, "state"));
+            }
+        }
+    }
+
+    fn fluid(&mut self, fluid: &Fluid, loc: String) {
+        if let Some(id) = &fluid.fluid {
+            if let Some(definition) = self.registries.fluid(id) {
+                self.state(definition, id, &fluid.state, &join(&loc, "state"));
+            }
+        }
+    }
+
+    fn entity(&mut self, entity: &Entity, loc: String) {
+        if let Some(TagOrId::Id(id)) = &entity.r#type {
+            self.check(RegistryKind::EntityType, id, join(&loc, "type"));
+        }
+        for effect in entity.effects.keys() {
+            self.check(RegistryKind::MobEffect, effect, join(&join(&loc, "effects"), &effect.to_string()));
+        }
+        if let Some(equipment) = &entity.equipment {
+            let slots = [
+                ("mainhand", &equipment.mainhand), ("offhand", &equipment.offhand),
+                ("head", &equipment.head), ("chest", &equipment.chest),
+                ("legs", &equipment.legs), ("feet", &equipment.feet),
+            ];
+            for (slot, item) in slots.iter() {
+                if let Some(item) = item {
+                    self.item(item, join(&join(&loc, "equipment"), slot));
+                }
+            }
+        }
+        if let Some(lightning_bolt) = &entity.lightning_bolt {
+            self.lightning_bolt(lightning_bolt, join(&loc, "lightning_bolt"));
+        }
+        if let Some(location) = &entity.location {
+            self.location(location, join(&loc, "location"));
+        }
+        if let Some(location) = &entity.stepping_on {
+            self.location(location, join(&loc, "stepping_on"));
+        }
+        if let Some(player) = &entity.player {
+            self.player(player, join(&loc, "player"));
+        }
+        match entity.type_specific.as_deref() {
+            Some(TypeSpecific::Player(player)) => self.player(player, join(&loc, "type_specific")),
+            Some(TypeSpecific::Lightning(lightning_bolt)) =>
+                self.lightning_bolt(lightning_bolt, join(&loc, "type_specific")),
+            _ => {}
+        }
+        let nested = [
+            ("passenger", &entity.passenger), ("targeted_entity", &entity.targeted_entity),
+            ("vehicle", &entity.vehicle),
+        ];
+        for (key, nested) in nested.iter() {
+            if let Some(nested) = nested {
+                self.entity(nested, join(&loc, key));
+            }
+        }
+    }
+
+    fn player(&mut self, player: &Player, loc: String) {
+        if let Some(looking_at) = &player.looking_at {
+            self.entity(looking_at, join(&loc, "looking_at"));
+        }
+        for (k, stat) in player.stats.iter().enumerate() {
+            self.check(RegistryKind::StatType, &stat.r#type, format!("{}[{}].type", join(&loc, "stats"), k));
+        }
+    }
+
+    /// The fields of the lightning bolt as an entity are flattened, and thus share `loc`.
+    fn lightning_bolt(&mut self, lightning_bolt: &LightningBolt, loc: String) {
+        if let Some(struck) = &lightning_bolt.entity_struck {
+            self.entity(struck, join(&loc, "entity_struck"));
+        }
+        self.entity(&lightning_bolt.lightning_entity, loc);
+    }
+
+    fn item(&mut self, item: &Item, loc: String) {
+        if let Some(items) = &item.items {
+            self.ids(RegistryKind::Item, items, join(&loc, "items"));
+        }
+        if let Some(potion) = &item.potion {
+            self.check(RegistryKind::Potion, potion, join(&loc, "potion"));
+        }
+        let predicates = join(&loc, "predicates");
+        if let Some(potion) = &item.predicates.potion_contents {
+            self.ids(RegistryKind::Potion, potion, join(&predicates, "minecraft:potion_contents"));
+        }
+        let sub_predicates = [("minecraft:enchantments", &item.predicates.enchantments),
+            ("minecraft:stored_enchantments", &item.predicates.stored_enchantments)];
+        for (key, enchantments) in sub_predicates.iter() {
+            for (k, enchantment) in enchantments.iter().enumerate() {
+                if let Some(ids) = &enchantment.enchantments {
+                    let loc = format!("{}[{}].enchantments", join(&predicates, key), k);
+                    self.ids(RegistryKind::Enchantment, ids, loc);
+                }
+            }
+        }
+        let enchantments = [("enchantments", &item.enchantments), ("stored_enchantments", &item.stored_enchantments)];
+        for (key, enchantments) in enchantments.iter() {
+            for (k, enchantment) in enchantments.iter().enumerate() {
+                if let Some(id) = &enchantment.enchantment {
+                    self.check(RegistryKind::Enchantment, id, format!("{}[{}].enchantment", join(&loc, key), k));
+                }
+            }
+        }
+    }
+
+    fn damage_source(&mut self, source: &DamageSource, loc: String) {
+        if let Some(entity) = &source.direct_entity {
+            self.entity(entity, join(&loc, "direct_entity"));
+        }
+        if let Some(entity) = &source.source_entity {
+            self.entity(entity, join(&loc, "source_entity"));
+        }
+    }
+
+    fn predicate(&mut self, predicate: &Predicate, loc: String) {
+        match predicate {
+            Predicate::AllOf { terms, .. } | Predicate::AnyOf { terms, .. } | Predicate::Alternative { terms, .. } => {
+                for (k, term) in terms.iter().enumerate() {
+                    self.predicate(term, format!("{}[{}]", join(&loc, "terms"), k));
+                }
+            }
+            Predicate::Inverted { term, .. } => self.predicate(term, join(&loc, "term")),
+            Predicate::BlockStateProperty { block, properties, .. } => {
+                self.check(RegistryKind::Block, block, join(&loc, "block"));
+                if let Some(definition) = self.registries.block(block) {
+                    let properties = properties.iter()
+                        .map(|(k, v)| (k.clone(), PlainValue::String(v.clone())))
+                        .collect();
+                    self.state(definition, block, &properties, &join(&loc, "properties"));
+                }
+            }
+            Predicate::DamageSourceProperties { predicate, .. } =>
+                self.damage_source(predicate, join(&loc, "predicate")),
+            Predicate::EntityProperties { predicate, .. } => self.entity(predicate, join(&loc, "predicate")),
+            Predicate::LocationCheck { predicate, .. } => self.location(predicate, join(&loc, "predicate")),
+            Predicate::MatchTool { predicate, .. } => self.item(predicate, join(&loc, "predicate")),
+            Predicate::RandomChanceWithEnchantedBonus { enchantment, .. } | Predicate::TableBonus { enchantment, .. } =>
+                self.check(RegistryKind::Enchantment, enchantment, join(&loc, "enchantment")),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Embedded registry entries for Minecraft 1.16.2–1.17.1, all in the `minecraft` namespace.
+//!
+//! Only small registries are listed here. Blocks, items, entity types and biomes, and the
+//! registries of newer versions, should be generated from the data generator reports rather than
+//! written by hand; see [`Registries::vanilla`](super::Registries::vanilla).
+
+use super::RegistryKind;
+
+const DIMENSIONS: &[&str] = &["overworld", "the_nether", "the_end"];
+
+const MOB_EFFECTS: &[&str] = &[
+    "speed", "slowness", "haste", "mining_fatigue", "strength", "instant_health",
+    "instant_damage", "jump_boost", "nausea", "regeneration", "resistance", "fire_resistance",
+    "water_breathing", "invisibility", "blindness", "night_vision", "hunger", "weakness",
+    "poison", "wither", "health_boost", "absorption", "saturation", "glowing", "levitation",
+    "luck", "unluck", "slow_falling", "conduit_power", "dolphins_grace", "bad_omen",
+    "hero_of_the_village",
+];
+
+const ENCHANTMENTS: &[&str] = &[
+    "protection", "fire_protection", "feather_falling", "blast_protection",
+    "projectile_protection", "respiration", "aqua_affinity", "thorns", "depth_strider",
+    "frost_walker", "binding_curse", "soul_speed", "sharpness", "smite", "bane_of_arthropods",
+    "knockback", "fire_aspect", "looting", "sweeping", "efficiency", "silk_touch", "unbreaking",
+    "fortune", "power", "punch", "flame", "infinity", "luck_of_the_sea", "lure", "loyalty",
+    "impaling", "riptide", "channeling", "multishot", "quick_charge", "piercing", "mending",
+    "vanishing_curse",
+];
+
+const POTIONS: &[&str] = &[
+    "empty", "water", "mundane", "thick", "awkward", "night_vision", "long_night_vision",
+    "invisibility", "long_invisibility", "leaping", "long_leaping", "strong_leaping",
+    "fire_resistance", "long_fire_resistance", "swiftness", "long_swiftness", "strong_swiftness",
+    "slowness", "long_slowness", "strong_slowness", "turtle_master", "long_turtle_master",
+    "strong_turtle_master", "water_breathing", "long_water_breathing", "healing",
+    "strong_healing", "harming", "strong_harming", "poison", "long_poison", "strong_poison",
+    "regeneration", "long_regeneration", "strong_regeneration", "strength", "long_strength",
+    "strong_strength", "weakness", "long_weakness", "luck", "slow_falling", "long_slow_falling",
+];
+
+const STRUCTURES: &[&str] = &[
+    "pillager_outpost", "mineshaft", "mansion", "jungle_pyramid", "desert_pyramid", "igloo",
+    "ruined_portal", "shipwreck", "swamp_hut", "stronghold", "monument", "ocean_ruin",
+    "fortress", "endcity", "buried_treasure", "village", "nether_fossil", "bastion_remnant",
+];
+
+const STAT_TYPES: &[&str] = &[
+    "mined", "crafted", "used", "broken", "picked_up", "dropped", "killed", "killed_by", "custom",
+];
+
+/// Embedded entries for Minecraft 1.16.2–1.17.1.
+pub(super) const V1_16_2: &[(RegistryKind, &[&str])] = &[
+    (RegistryKind::Dimension, DIMENSIONS),
+    (RegistryKind::MobEffect, MOB_EFFECTS),
+    (RegistryKind::Enchantment, ENCHANTMENTS),
+    (RegistryKind::Potion, POTIONS),
+    (RegistryKind::Structure, STRUCTURES),
+    (RegistryKind::StatType, STAT_TYPES),
+];
+
+/// A state property and its allowed values.
+type Property = (&'static str, &'static [&'static str]);
+
+const FALLING: Property = ("falling", &["true", "false"]);
+const LEVEL: Property = ("level", &["1", "2", "3", "4", "5", "6", "7", "8"]);
+
+/// Embedded fluid definitions for Minecraft 1.16.2–1.17.1.
+pub(super) const FLUIDS_1_16_2: &[(&str, &[Property])] = &[
+    ("empty", &[]),
+    ("flowing_water", &[FALLING, LEVEL]),
+    ("water", &[FALLING]),
+    ("flowing_lava", &[FALLING, LEVEL]),
+    ("lava", &[FALLING]),
+];
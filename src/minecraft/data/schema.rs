@@ -0,0 +1,277 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! JSON Schema documents for all resource types, generated from the type definitions, with
+//! descriptions taken from the doc comments. Requires the `schema` feature.
+//!
+//! The schemas describe vanilla resources only: custom triggers and predicate conditions (see
+//! [`extension`](crate::minecraft::data::extension)) are not included.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::pack::ResourceKind;
+//! # use minecraft_json::minecraft::data::schema;
+//! let schema = serde_json::to_value(schema::resource(ResourceKind::Advancement).unwrap()).unwrap();
+//! assert_eq!(schema["title"], "Advancement");
+//! let frames = schema["definitions"]["Frame"]["oneOf"].as_array().unwrap();
+//! assert_eq!(frames[0]["enum"], serde_json::json!(["challenge"]));
+//! assert_eq!(frames[2]["description"], "A normal tile (default).");
+//! assert!(schema::resource(ResourceKind::Function).is_none());
+//! ```
+//!
+//! The descriptions can also be looked up for a node in a resource, e.g. for hover docs in an
+//! editor, see [`describe`].
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use schemars::JsonSchema;
+use schemars::gen::SchemaSettings;
+use schemars::schema::{RootSchema, SchemaObject};
+use schemars::visit::{self, Visitor};
+use serde_json::Value;
+use crate::minecraft::data::advancement::Advancement;
+use crate::minecraft::data::item_modifier::ItemModifier;
+use crate::minecraft::data::loot_table::LootTable;
+use crate::minecraft::data::meta::McMeta;
+use crate::minecraft::data::pack::ResourceKind;
+use crate::minecraft::data::predicate::PredicateFile;
+use crate::minecraft::data::recipe::Recipe;
+use crate::minecraft::data::tag::{Tag, TagKind};
+use crate::minecraft::text::TextComponent;
+
+/// Schema for a kind of resource, `None` for non-JSON resources (i.e. functions).
+pub fn resource(kind: ResourceKind) -> Option<RootSchema> {
+    Some(match kind {
+        ResourceKind::Advancement => generate::<Advancement>(),
+        ResourceKind::Predicate => generate::<PredicateFile>(),
+        ResourceKind::LootTable => generate::<LootTable>(),
+        ResourceKind::ItemModifier => generate::<ItemModifier>(),
+        ResourceKind::Recipe => generate::<Recipe>(),
+        ResourceKind::Function => return None,
+        ResourceKind::Tag(_) => generate::<Tag>(),
+    })
+}
+
+/// Schema for `pack.mcmeta`.
+pub fn mcmeta() -> RootSchema { generate::<McMeta>() }
+
+/// Schema for JSON text components.
+pub fn text_component() -> RootSchema { generate::<TextComponent>() }
+
+/// Schema for `T`, with code examples left out of the descriptions.
+fn generate<T: JsonSchema>() -> RootSchema {
+    SchemaSettings::draft07().with_visitor(StripCode).into_generator().into_root_schema_for::<T>()
+}
+
+/// Remove code blocks from all descriptions, see [`strip_code`].
+#[derive(Debug, Clone)]
+struct StripCode;
+
+impl Visitor for StripCode {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(description) = schema.metadata.as_mut().and_then(|metadata| metadata.description.as_mut()) {
+            *description = strip_code(description);
+        }
+        visit::visit_schema_object(self, schema);
+    }
+}
+
+/// All schemas, keyed by a file name stem, e.g. `advancement` or `pack.mcmeta`.
+pub fn all() -> Vec<(&'static str, RootSchema)> {
+    let mut schemas = vec![("pack.mcmeta", mcmeta()), ("text_component", text_component())];
+    let kinds = [
+        ("advancement", ResourceKind::Advancement),
+        ("predicate", ResourceKind::Predicate),
+        ("loot_table", ResourceKind::LootTable),
+        ("item_modifier", ResourceKind::ItemModifier),
+        ("recipe", ResourceKind::Recipe),
+        ("tag", ResourceKind::Tag(TagKind::Blocks)),
+    ];
+    schemas.extend(kinds.iter().filter_map(|(name, kind)| Some((*name, resource(*kind)?))));
+    schemas
+}
+
+/// Write [`all`] schemas into `dir` as `<name>.schema.json`, creating `dir` if needed. As for
+/// [`describe`], code examples are left out of the descriptions.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::schema;
+/// let dir = std::env::temp_dir().join("minecraft-json-schemas");
+/// schema::write_all(&dir).unwrap();
+/// let json = std::fs::read_to_string(dir.join("advancement.schema.json")).unwrap();
+/// assert!(json.contains("Triggers when the player enters a bed."));
+/// for (name, _) in schema::all() {
+///     let json = std::fs::read_to_string(dir.join(format!("{}.schema.json", name))).unwrap();
+///     assert!(!json.contains("```") && !json.contains("# use"));
+/// }
+/// ```
+pub fn write_all(dir: impl AsRef<Path>) -> io::Result<()> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    for (name, schema) in all() {
+        let mut json = serde_json::to_string_pretty(&schema)?;
+        json.push('\n');
+        fs::write(dir.join(format!("{}.schema.json", name)), json)?;
+    }
+    Ok(())
+}
+
+/// Description of the node at `path` in `value`, a resource described by `schema`: the doc
+/// comment of the field, or else that of its type, or else that of the enum variant containing
+/// it. The path is a dot-separated path of JSON keys, with array indices in brackets, and may
+/// point past the end of `value`. Code examples are left out.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::pack::ResourceKind;
+/// # use minecraft_json::minecraft::data::schema;
+/// let schema = schema::resource(ResourceKind::Advancement).unwrap();
+/// let value = serde_json::json!({
+///   "criteria": {"slept": {"trigger": "slept_in_bed", "conditions": {}}}
+/// });
+/// let describe = |path| schema::describe(&schema, &value, path).unwrap();
+/// assert_eq!(describe("criteria.slept.trigger"), "Triggers when the player enters a bed.");
+/// assert!(describe("criteria.slept.conditions.location").starts_with("The location of the player."));
+/// assert!(describe("parent").starts_with("The optional parent advancement"));
+/// assert_eq!(describe("display.frame"), "The optional type of frame for the icon.");
+/// ```
+pub fn describe(schema: &RootSchema, value: &Value, path: &str) -> Option<String> {
+    describe_value(&serde_json::to_value(schema).ok()?, value, path)
+}
+
+/// Same as [`describe`], but with the schema already serialized, so that callers describing many
+/// paths can serialize it only once.
+pub fn describe_value(root: &Value, value: &Value, path: &str) -> Option<String> {
+    let definitions = &root["definitions"];
+    let (mut node, mut variant, mut value, mut rest) = (root, None, Some(value), path);
+    while !rest.is_empty() {
+        let (segment, tail) = next_segment(value, rest);
+        let (child, child_variant) = child(definitions, node, value, &segment)?;
+        node = child;
+        variant = child_variant.or(variant);
+        value = value.and_then(|value| match segment {
+            Segment::Key(key) => value.get(key),
+            Segment::Index(index) => value.get(index),
+        });
+        rest = tail;
+    }
+    let description = doc(definitions, node).or(variant)?;
+    Some(strip_code(description))
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Split the first segment off `path`. Keys may contain dots, so prefer keys present in `value`.
+fn next_segment<'a>(value: Option<&Value>, path: &'a str) -> (Segment<'a>, &'a str) {
+    let tail = |rest: &'a str| rest.strip_prefix('.').unwrap_or(rest);
+    if let Some(rest) = path.strip_prefix('[') {
+        if let Some((index, rest)) = rest.split_once(']') {
+            if let Ok(index) = index.parse() {
+                return (Segment::Index(index), tail(rest));
+            }
+        }
+    }
+    let keys = value.and_then(Value::as_object).into_iter().flat_map(|obj| obj.keys());
+    let known = keys.filter(|key| path.strip_prefix(key.as_str())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '['])))
+        .max_by_key(|key| key.len());
+    let len = known.map_or_else(|| path.find(['.', '[']).unwrap_or(path.len()), String::len);
+    (Segment::Key(&path[..len]), tail(&path[len..]))
+}
+
+fn resolve<'a>(definitions: &'a Value, mut schema: &'a Value) -> &'a Value {
+    while let Some(reference) = schema["$ref"].as_str() {
+        match definitions.get(reference.trim_start_matches("#/definitions/")) {
+            Some(target) => schema = target,
+            None => break,
+        }
+    }
+    schema
+}
+
+/// Schema of a child node, with the description of the enum variant it is found in, if any.
+fn child<'a>(definitions: &'a Value, schema: &'a Value, value: Option<&Value>, segment: &Segment)
+             -> Option<(&'a Value, Option<&'a str>)> {
+    let schema = resolve(definitions, schema);
+    let direct = match segment {
+        Segment::Key(key) => schema["properties"].get(key),
+        Segment::Index(index) => match &schema["items"] {
+            Value::Array(items) => items.get(*index),
+            items => Some(items).filter(|items| items.is_object()),
+        },
+    };
+    if let Some(direct) = direct {
+        return Some((direct, None));
+    }
+    for alternatives in ["allOf", "anyOf", "oneOf"].iter().filter_map(|key| schema[key].as_array()) {
+        for alternative in alternatives.iter().filter(|alt| matches_tags(definitions, alt, value)) {
+            if let Some((child, variant)) = child(definitions, alternative, value, segment) {
+                return Some((child, variant.or_else(|| alternative["description"].as_str())));
+            }
+        }
+    }
+    match segment {
+        Segment::Key(_) => Some((&schema["additionalProperties"], None))
+            .filter(|(additional, _)| additional.is_object()),
+        Segment::Index(_) => None,
+    }
+}
+
+/// Does `value` agree with the tags (properties with a single allowed value, possibly in several
+/// spellings, e.g. with or without the `minecraft:` namespace) in `schema`?
+fn matches_tags(definitions: &Value, schema: &Value, value: Option<&Value>) -> bool {
+    let normalise = |v: &Value| v.as_str().map(|s| s.trim_start_matches("minecraft:").to_string());
+    let properties = resolve(definitions, schema)["properties"].as_object();
+    properties.into_iter().flatten().all(|(key, property)| {
+        let tag = match property["enum"].as_array().map(Vec::as_slice) {
+            Some([tag, others @ ..]) if others.iter().all(|v| normalise(v).is_some() && normalise(v) == normalise(tag)) =>
+                tag,
+            _ => return true,
+        };
+        match value.and_then(|value| value.get(key)) {
+            Some(actual) => actual == tag || (normalise(actual).is_some() && normalise(actual) == normalise(tag)),
+            None => true,
+        }
+    })
+}
+
+fn doc<'a>(definitions: &'a Value, schema: &'a Value) -> Option<&'a str> {
+    if let Some(description) = schema["description"].as_str() {
+        return Some(description);
+    }
+    let target = resolve(definitions, schema);
+    if !std::ptr::eq(target, schema) {
+        return doc(definitions, target);
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        let alternatives = schema[key].as_array().into_iter().flatten();
+        let mut meaningful = alternatives.filter(|alt| alt["type"] != "null");
+        if let (Some(only), None) = (meaningful.next(), meaningful.next()) {
+            return doc(definitions, only);
+        }
+    }
+    None
+}
+
+/// Remove code blocks, which the generated descriptions keep on a single line.
+fn strip_code(description: &str) -> String {
+    let text: String = description.split("```").step_by(2).collect::<Vec<_>>().join(" ");
+    text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()).collect::<Vec<_>>().join("\n\n")
+}
@@ -0,0 +1,430 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Tags are used to group blocks, items, entity types, fluids, game events or functions together
+//! for other data pack files to refer to. They are a part of data packs.
+//!
+//! Inside a data pack, a tag is located within `data/<namespace>/tags/<kind>`, see [`TagKind`].
+//!
+//! ```
+//! # use minecraft_json::assert_equiv_pretty;
+//! # use minecraft_json::minecraft::data::tag::{Tag, TagEntry};
+//! # use minecraft_json::minecraft::common::Extra;
+//! assert_equiv_pretty!(r##"{
+//!   "values": [
+//!     "minecraft:oak_log",
+//!     "#minecraft:birch_logs",
+//!     {
+//!       "id": "othermod:palm_log",
+//!       "required": false
+//!     }
+//!   ]
+//! }"##, Tag {
+//!     replace: false,
+//!     values: vec![
+//!         TagEntry::Id("minecraft:oak_log".parse().unwrap()),
+//!         TagEntry::Id("#minecraft:birch_logs".parse().unwrap()),
+//!         TagEntry::Entry {
+//!             id: "othermod:palm_log".parse().unwrap(),
+//!             required: false,
+//!         },
+//!     ],
+//!     extra: Extra::default(),
+//! });
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use serde::{Serialize, Deserialize, Deserializer};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use crate::defaults;
+use crate::minecraft::common::{Either, ResourceLocation, TagOrId, Extra};
+use crate::minecraft::data::conditions::{Block, Item, Fluid};
+use crate::minecraft::data::meta::pack_format;
+use crate::minecraft::error;
+
+/// A tag JSON file.
+#[derive(Eq, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Tag {
+    /// Whether or not the contents of this tag should completely replace tag contents from
+    /// different lower priority data packs with the same resource location. When `false` the
+    /// tag's content is appended to the contents of the higher priority data packs, instead.
+    #[serde(default, skip_serializing_if = "defaults::is_false")]
+    pub replace: bool,
+    /// A list of mix and match of object names and tag names. For tags, recursive reference is
+    /// possible, but a circular reference causes a loading failure.
+    pub values: Vec<TagEntry>,
+    /// Fields not known to this crate, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// An entry in the `values` list of a [`Tag`].
+#[derive(Eq, PartialEq, Debug)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum TagEntry {
+    /// An object name, or a tag name prefixed with `#`.
+    Id(TagOrId),
+    /// An entry in object form.
+    Entry {
+        /// An object name, or a tag name prefixed with `#`.
+        id: TagOrId,
+        /// Whether or not loading this tag should fail if this entry is not found, `true` by
+        /// default (also for the string entries). A tag that fails to load can still be
+        /// referenced in any data pack and be (re)defined in other data packs.
+        #[serde(default = "defaults::r#true", skip_serializing_if = "defaults::is_true")]
+        required: bool,
+    },
+}
+
+impl<'de> Deserialize<'de> for TagEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Entry {
+            id: TagOrId,
+            #[serde(default = "defaults::r#true")]
+            required: bool,
+        }
+        error::untagged(deserializer, "TagEntry", &[
+            &|v| error::variant(v, TagEntry::Id),
+            &|v| error::variant(v, |Entry { id, required }| TagEntry::Entry { id, required }),
+        ])
+    }
+}
+
+impl TagEntry {
+    /// The object name or tag name (prefixed with `#`) of this entry.
+    pub fn id(&self) -> &TagOrId {
+        match self {
+            TagEntry::Id(id) => id,
+            TagEntry::Entry { id, .. } => id,
+        }
+    }
+
+    /// Whether or not loading the tag should fail if this entry is not found.
+    pub fn is_required(&self) -> bool {
+        match self {
+            TagEntry::Id(_) => true,
+            TagEntry::Entry { required, .. } => *required,
+        }
+    }
+
+    /// The tag this entry refers to (without the `#` prefix), if any.
+    pub fn referenced_tag(&self) -> Option<&ResourceLocation> {
+        match self.id() {
+            TagOrId::Tag(tag) => Some(tag),
+            TagOrId::Id(_) => None,
+        }
+    }
+}
+
+/// Kinds of tags, each in its own directory under `data/<namespace>/tags`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Hash)]
+pub enum TagKind {
+    /// Block tags, in `tags/blocks`.
+    Blocks,
+    /// Item tags, in `tags/items`.
+    Items,
+    /// Fluid tags, in `tags/fluids`.
+    Fluids,
+    /// Entity type tags, in `tags/entity_types`.
+    EntityTypes,
+    /// Function tags, in `tags/functions`.
+    Functions,
+    /// Game event tags, in `tags/game_events`.
+    GameEvents,
+}
+
+impl TagKind {
+    /// All kinds of tags.
+    pub const ALL: [TagKind; 6] = [
+        TagKind::Blocks, TagKind::Items, TagKind::Fluids,
+        TagKind::EntityTypes, TagKind::Functions, TagKind::GameEvents,
+    ];
+
+    /// The directory name for this kind of tag under `data/<namespace>/tags`, before Minecraft
+    /// 1.21.
+    pub fn directory(self) -> &'static str {
+        match self {
+            TagKind::Blocks => "blocks",
+            TagKind::Items => "items",
+            TagKind::Fluids => "fluids",
+            TagKind::EntityTypes => "entity_types",
+            TagKind::Functions => "functions",
+            TagKind::GameEvents => "game_events",
+        }
+    }
+
+    /// The directory name for this kind of tag under `data/<namespace>/tags`, as of a
+    /// [`pack_format`]. Directory names are singular since Minecraft 1.21.
+    pub fn directory_for(self, pack_format: i64) -> &'static str {
+        if pack_format < pack_format::VER_48 { return self.directory(); }
+        match self {
+            TagKind::Blocks => "block",
+            TagKind::Items => "item",
+            TagKind::Fluids => "fluid",
+            TagKind::EntityTypes => "entity_type",
+            TagKind::Functions => "function",
+            TagKind::GameEvents => "game_event",
+        }
+    }
+
+    /// Find the kind of tag stored in a directory under `data/<namespace>/tags`, accepting both
+    /// the plural and the singular directory names.
+    pub fn from_directory(dir: &str) -> Option<TagKind> {
+        TagKind::ALL.iter().copied()
+            .find(|k| k.directory() == dir || k.directory_for(pack_format::VER_48) == dir)
+    }
+}
+
+/// Errors in tag resolution.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum TagError {
+    /// A tag is referenced but not defined.
+    Missing {
+        /// The missing tag.
+        tag: String,
+        /// The tag that contains the reference, if the reference is from another tag.
+        referenced_by: Option<String>,
+    },
+    /// Tags reference each other in a circle.
+    Cycle(
+        /// The tags involved, in reference order; the last one references the first one.
+        Vec<String>,
+    ),
+}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TagError::Missing { tag, referenced_by: None } =>
+                write!(f, "tag '#{}' is not defined", tag),
+            TagError::Missing { tag, referenced_by: Some(parent) } =>
+                write!(f, "tag '#{}' referenced by '#{}' is not defined", tag, parent),
+            TagError::Cycle(tags) => {
+                write!(f, "circular tag reference: ")?;
+                for tag in tags {
+                    write!(f, "#{} -> ", tag)?;
+                }
+                write!(f, "#{}", tags[0])
+            }
+        }
+    }
+}
+
+impl std::error::Error for TagError {}
+
+/// All tags of a single [`TagKind`], keyed by their namespaced IDs.
+///
+/// ```
+/// # use minecraft_json::minecraft::data::tag::{Tag, TagEntry, TagSet, TagError};
+/// let mut tags = TagSet::default();
+/// tags.insert("my:fruits", serde_json::from_str(r##"{
+///   "values": ["minecraft:apple", "#my:berries"]
+/// }"##).unwrap());
+/// tags.insert("my:berries", serde_json::from_str(r##"{
+///   "values": ["minecraft:sweet_berries", "glow_berries"]
+/// }"##).unwrap());
+/// let fruits = tags.resolve("my:fruits").unwrap();
+/// assert!(fruits.contains("minecraft:glow_berries"));
+/// assert_eq!(fruits.len(), 3);
+///
+/// tags.insert("my:berries", serde_json::from_str(r##"{
+///   "values": ["#my:fruits"]
+/// }"##).unwrap());
+/// assert!(matches!(tags.resolve("my:fruits"), Err(TagError::Cycle(_))));
+/// ```
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct TagSet {
+    tags: BTreeMap<String, Tag>,
+}
+
+impl TagSet {
+    /// Add a tag, merging it with any previously added tag of the same ID according to
+    /// [`Tag::replace`]. Tags should be inserted from lower priority packs to higher ones. When
+    /// merging, unknown fields are merged too, those of the later tag taking precedence.
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::tag::TagSet;
+    /// let mut tags = TagSet::default();
+    /// tags.insert("my:fruits", serde_json::from_str(r#"{"values": ["apple"], "a": 1, "b": 1}"#).unwrap());
+    /// tags.insert("my:fruits", serde_json::from_str(r#"{"values": ["melon"], "b": 2}"#).unwrap());
+    /// let fruits = tags.get("my:fruits").unwrap();
+    /// assert_eq!(fruits.values.len(), 2);
+    /// assert_eq!(serde_json::Value::Object(fruits.extra.0.clone()), serde_json::json!({"a": 1, "b": 2}));
+    /// ```
+    pub fn insert(&mut self, id: &str, tag: Tag) {
+        let id = ResourceLocation::normalise(id);
+        match self.tags.get_mut(&id) {
+            Some(old) if !tag.replace => {
+                old.values.extend(tag.values);
+                old.extra.0.extend(tag.extra.0);
+            }
+            _ => { self.tags.insert(id, tag); }
+        }
+    }
+
+    /// Remove a tag, with everything merged into it, e.g. to add a changed tag file again.
+    pub fn remove(&mut self, id: &str) -> Option<Tag> {
+        self.tags.remove(&ResourceLocation::normalise(id))
+    }
+
+    /// Get a tag by ID, without resolving nested references.
+    pub fn get(&self, id: &str) -> Option<&Tag> {
+        self.tags.get(&ResourceLocation::normalise(id))
+    }
+
+    /// Whether or not a tag is defined.
+    pub fn contains_tag(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Iterate over all tags in this set.
+    pub fn iter(&self) -> impl Iterator<Item=(&str, &Tag)> {
+        self.tags.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Expand a tag into the set of concrete IDs it contains, following nested `#` references.
+    ///
+    /// Missing optional entries (`"required": false`) are skipped, missing required tags and
+    /// circular references are reported as errors. Each nested tag is expanded only once, even
+    /// if it is referenced many times:
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::data::tag::TagSet;
+    /// let mut tags = TagSet::default();
+    /// for k in 0..64 {
+    ///     let diamond = format!(r##"{{"values": ["#my:left{0}", "#my:right{0}"]}}"##, k);
+    ///     let next = format!(r##"{{"values": ["#my:top{}"]}}"##, k + 1);
+    ///     tags.insert(&format!("my:top{}", k), serde_json::from_str(&diamond).unwrap());
+    ///     tags.insert(&format!("my:left{}", k), serde_json::from_str(&next).unwrap());
+    ///     tags.insert(&format!("my:right{}", k), serde_json::from_str(&next).unwrap());
+    /// }
+    /// tags.insert("my:top64", serde_json::from_str(r#"{"values": ["stone"]}"#).unwrap());
+    /// assert_eq!(tags.resolve("my:top0").unwrap().len(), 1);
+    /// ```
+    pub fn resolve(&self, id: &str) -> Result<BTreeSet<String>, TagError> {
+        let mut result = BTreeSet::new();
+        let mut stack = Vec::new();
+        let mut done = BTreeSet::new();
+        self.resolve_into(&ResourceLocation::normalise(id), None, true, &mut stack, &mut done, &mut result)?;
+        Ok(result)
+    }
+
+    /// Expand the tag `id` into `result`, unless it is already `done`, i.e. fully expanded.
+    fn resolve_into(&self, id: &str, parent: Option<&str>, required: bool, stack: &mut Vec<String>,
+                    done: &mut BTreeSet<String>, result: &mut BTreeSet<String>) -> Result<(), TagError> {
+        if done.contains(id) {
+            return Ok(());
+        }
+        if let Some(k) = stack.iter().position(|t| t == id) {
+            return Err(TagError::Cycle(stack[k..].to_vec()));
+        }
+        let tag = match self.tags.get(id) {
+            Some(tag) => tag,
+            None if required => return Err(TagError::Missing {
+                tag: id.to_string(),
+                referenced_by: parent.map(str::to_string),
+            }),
+            None => return Ok(()),
+        };
+        stack.push(id.to_string());
+        for entry in &tag.values {
+            match entry.referenced_tag() {
+                Some(nested) => self.resolve_into(
+                    &nested.to_string(), Some(id), entry.is_required(), stack, done, result)?,
+                None => { result.insert(entry.id().location().to_string()); }
+            }
+        }
+        stack.pop();
+        done.insert(id.to_string());
+        Ok(())
+    }
+
+    /// Check whether a concrete ID is contained in a tag.
+    pub fn tag_contains(&self, tag: &str, id: &str) -> Result<bool, TagError> {
+        Ok(self.resolve(tag)?.contains(&ResourceLocation::normalise(id)))
+    }
+
+    /// Check that a tag referenced from somewhere else is defined and resolves correctly.
+    pub fn check_reference(&self, tag: &str) -> Result<(), TagError> {
+        self.resolve(tag).map(|_| ())
+    }
+}
+
+/// All tags in a data pack, one [`TagSet`] per [`TagKind`].
+#[derive(Eq, PartialEq, Debug, Default)]
+#[allow(missing_docs)]
+pub struct Tags {
+    pub blocks: TagSet,
+    pub items: TagSet,
+    pub fluids: TagSet,
+    pub entity_types: TagSet,
+    pub functions: TagSet,
+    pub game_events: TagSet,
+}
+
+impl Tags {
+    /// Get the tags of some kind.
+    pub fn get(&self, kind: TagKind) -> &TagSet {
+        match kind {
+            TagKind::Blocks => &self.blocks,
+            TagKind::Items => &self.items,
+            TagKind::Fluids => &self.fluids,
+            TagKind::EntityTypes => &self.entity_types,
+            TagKind::Functions => &self.functions,
+            TagKind::GameEvents => &self.game_events,
+        }
+    }
+
+    /// Get the tags of some kind for modification.
+    pub fn get_mut(&mut self, kind: TagKind) -> &mut TagSet {
+        match kind {
+            TagKind::Blocks => &mut self.blocks,
+            TagKind::Items => &mut self.items,
+            TagKind::Fluids => &mut self.fluids,
+            TagKind::EntityTypes => &mut self.entity_types,
+            TagKind::Functions => &mut self.functions,
+            TagKind::GameEvents => &mut self.game_events,
+        }
+    }
+
+    /// Check the block tag used in a [`Block`] condition, if any.
+    pub fn check_block(&self, block: &Block) -> Result<(), TagError> {
+        block.tag.as_ref().map_or(Ok(()), |t| self.blocks.check_reference(&t.to_string()))
+    }
+
+    /// Check the item tags used in an [`Item`] condition (in `tag` or in `items`), if any.
+    pub fn check_item(&self, item: &Item) -> Result<(), TagError> {
+        let items = match &item.items {
+            Some(Either::Left(TagOrId::Tag(tag))) => Some(tag),
+            _ => None,
+        };
+        item.tag.iter().chain(items).try_for_each(|t| self.items.check_reference(&t.to_string()))
+    }
+
+    /// Check the fluid tag used in a [`Fluid`] condition, if any.
+    pub fn check_fluid(&self, fluid: &Fluid) -> Result<(), TagError> {
+        fluid.tag.as_ref().map_or(Ok(()), |t| self.fluids.check_reference(&t.to_string()))
+    }
+}
@@ -0,0 +1,460 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Upgrading data packs to a newer [`pack_format`].
+//!
+//! Each migration applies when the upgrade crosses the `pack_format` that introduced the change.
+//! Migrations work on the JSON text of resources, so that fields not modelled in this crate are
+//! preserved. Constructs that cannot be converted automatically are reported as
+//! [`UpgradeIssue`]s and left as they are.
+//!
+//! | `pack_format` | Migration                                                                   |
+//! |---------------|-----------------------------------------------------------------------------|
+//! | 6             | entity conditions in advancement criteria become lists of predicates        |
+//! | 10            | `fishing_hook`, `lightning_bolt` and `player` in entity conditions move to `type_specific` |
+//! | 15            | the `alternative` predicate condition becomes `any_of`                      |
+//! | 41            | `tag`, `nbt`, `potion`, `durability` and enchantments in item conditions move to `items` and `predicates` |
+//! | 48            | directory names become singular; `random_chance_with_looting` becomes `random_chance_with_enchanted_bonus` |
+//! | 71            | `clickEvent` and `hoverEvent` in text components become `click_event` and `hover_event` |
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::meta::pack_format;
+//! # use minecraft_json::minecraft::data::upgrade::upgrade_file;
+//! let mut json = serde_json::from_str(r#"{
+//!   "condition": "alternative",
+//!   "terms": [{"condition": "match_tool", "predicate": {"tag": "minecraft:axes"}}]
+//! }"#).unwrap();
+//! let (path, issues) = upgrade_file("data/demo/predicates/p.json", &mut json,
+//!                                   pack_format::VER_7, pack_format::VER_48);
+//! assert_eq!(path, "data/demo/predicate/p.json");
+//! assert!(issues.is_empty());
+//! assert_eq!(json, serde_json::json!({
+//!   "condition": "minecraft:any_of",
+//!   "terms": [{"condition": "match_tool", "predicate": {"items": "#minecraft:axes"}}]
+//! }));
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+use serde_json::{Map, Number, Value};
+use crate::minecraft::common::Either;
+use crate::minecraft::data::advancement::Criterion;
+use crate::minecraft::data::meta::pack_format;
+use crate::minecraft::data::pack::{self, DataPack, LoadError, LoadResult, ResourceKind};
+use crate::minecraft::data::version::{self, Model};
+use crate::minecraft::span::join;
+
+/// A construct that cannot be upgraded automatically.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct UpgradeIssue {
+    /// Path of the file relative to the pack root, as of the target version.
+    pub file: String,
+    /// Location inside the file, as a dot-separated path of JSON keys, with array indices in
+    /// brackets.
+    pub location: String,
+    /// What could not be converted, and why.
+    pub message: String,
+}
+
+impl Display for UpgradeIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.file)?;
+        if !self.location.is_empty() { write!(f, ": {}", self.location)?; }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for UpgradeIssue {}
+
+/// A data pack upgraded to a newer `pack_format`, as files paired with their paths relative to the
+/// pack root.
+#[derive(Eq, PartialEq, Debug)]
+pub struct Upgraded {
+    /// All files of the upgraded pack, `pack.mcmeta` first.
+    pub files: Vec<(String, String)>,
+    /// Constructs that need manual attention.
+    pub issues: Vec<UpgradeIssue>,
+}
+
+impl Upgraded {
+    /// Write the upgraded pack as a directory tree rooted at `root`, see [`DataPack::write`].
+    pub fn write(&self, root: impl AsRef<Path>) -> io::Result<()> {
+        pack::write_files(root.as_ref(), self.files.clone())
+    }
+
+    /// Load the upgraded files back into a [`DataPack`].
+    pub fn into_pack(self) -> LoadResult {
+        let mut files = self.files.into_iter();
+        let meta = match files.next() {
+            Some((path, meta)) if path == "pack.mcmeta" => pack::parse(&path, &meta)?,
+            _ => return Err(LoadError {
+                path: "pack.mcmeta".to_string(),
+                error: pack::LoadErrorKind::Io(io::ErrorKind::NotFound.into()),
+            }),
+        };
+        let mut pack = DataPack::new(meta);
+        let mut errors = Vec::new();
+        for (path, contents) in files {
+            pack.add_file(&path, &contents, &mut errors);
+        }
+        Ok((pack, errors))
+    }
+}
+
+/// Upgrade a data pack to the `target` pack format. Downgrading is not supported: the files are
+/// only moved to the directories of the target version, and an issue is reported.
+///
+/// ```
+/// # use maplit::btreemap;
+/// # use minecraft_json::minecraft::data::pack::DataPack;
+/// # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+/// # use minecraft_json::minecraft::data::advancement::{Advancement, Criterion, TriggerSpelling};
+/// # use minecraft_json::minecraft::data::conditions::Entity;
+/// # use minecraft_json::minecraft::data::upgrade::upgrade_pack;
+/// # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+/// # use minecraft_json::minecraft::common::{Either, Extra};
+/// let mut pack = DataPack::new(McMeta::Pack {
+///     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+///     pack_format: pack_format::VER_5,
+/// });
+/// pack.advancements.insert("demo:sleep".to_string(), Advancement {
+///     display: None,
+///     parent: None,
+///     criteria: btreemap! {
+///         "slept".to_string() => Criterion::SleptInBed {
+///             location: None,
+///             player: Some(Either::Right(Box::new(Entity::default()))),
+///             spelling: TriggerSpelling::Namespaced,
+///             extra: Extra::default(),
+///         },
+///     },
+///     requirements: Vec::new(),
+///     rewards: None,
+///     extra: Extra::default(),
+/// });
+/// pack.predicates.insert("demo:axe".to_string(), serde_json::from_str(r#"{
+///   "condition": "minecraft:match_tool",
+///   "predicate": {"tag": "minecraft:axes", "durability": {"min": 1}}
+/// }"#).unwrap());
+/// let upgraded = upgrade_pack(&pack, pack_format::VER_48).unwrap();
+/// assert!(upgraded.issues.is_empty());
+/// assert_eq!(upgraded.files[1].0, "data/demo/advancement/sleep.json");
+/// assert_eq!(upgraded.files[2].0, "data/demo/predicate/axe.json");
+/// let (upgraded, errors) = upgraded.into_pack().unwrap();
+/// assert!(errors.is_empty());
+/// assert_eq!(upgraded.meta.pack_format(), pack_format::VER_48);
+/// match &upgraded.advancements["demo:sleep"].criteria["slept"] {
+///     Criterion::SleptInBed { player: Some(Either::Left(predicates)), .. } =>
+///         assert_eq!(predicates.len(), 1),
+///     _ => unreachable!(),
+/// }
+/// assert!(upgraded.predicates.contains_key("demo:axe"));
+/// ```
+pub fn upgrade_pack(pack: &DataPack, target: i64) -> serde_json::Result<Upgraded> {
+    let from = pack.meta.pack_format();
+    let mut issues = Vec::new();
+    if target < from {
+        issues.push(UpgradeIssue {
+            file: "pack.mcmeta".to_string(),
+            location: "pack.pack_format".to_string(),
+            message: format!("cannot downgrade from pack_format {} to {}", from, target),
+        });
+    }
+    let mut files = Vec::new();
+    for (path, contents) in pack.to_files()? {
+        if !path.ends_with(".json") && path != "pack.mcmeta" {
+            let path = ResourceKind::from_path(&path)
+                .map_or(path, |(kind, id)| kind.path_for(&id, target));
+            files.push((path, contents));
+            continue;
+        }
+        let mut value: Value = serde_json::from_str(&contents)?;
+        let (path, file_issues) = upgrade_file(&path, &mut value, from, target);
+        issues.extend(file_issues);
+        files.push((path, pack::to_json(&value)?));
+    }
+    Ok(Upgraded { files, issues })
+}
+
+/// Upgrade a single JSON file, given its path relative to the pack root, from the `from` pack
+/// format to the `target` pack format. Returns the path of the file in the target version,
+/// together with constructs that need manual attention.
+pub fn upgrade_file(path: &str, value: &mut Value, from: i64, target: i64)
+                    -> (String, Vec<UpgradeIssue>) {
+    let resource = ResourceKind::from_path(path);
+    let file = resource.as_ref().map_or(path.to_string(), |(kind, id)| kind.path_for(id, target));
+    let mut upgrader = Upgrader { file: file.clone(), issues: Vec::new() };
+    let crosses = |version| from < version && version <= target;
+    if path == "pack.mcmeta" {
+        if let Some(pack) = value.get_mut("pack").and_then(Value::as_object_mut) {
+            pack.insert("pack_format".to_string(), Value::from(target));
+        }
+    }
+    if crosses(pack_format::VER_6) && matches!(resource, Some((ResourceKind::Advancement, _))) {
+        upgrader.criteria(value);
+    }
+    let model = match &resource {
+        _ if path == "pack.mcmeta" => Some(Model::McMeta),
+        Some((kind, _)) => Model::of(*kind),
+        None => None,
+    };
+    let model = match model {
+        Some(model) => model,
+        None => return (file, upgrader.issues),
+    };
+    if crosses(pack_format::VER_10) {
+        upgrader.each(model, value, Model::Entity, Upgrader::type_specific);
+    }
+    if crosses(pack_format::VER_15) {
+        upgrader.each(model, value, Model::Predicate, |_, obj, _| {
+            if condition_is(obj, "alternative") {
+                obj.insert("condition".to_string(), Value::from("minecraft:any_of"));
+            }
+        });
+    }
+    if crosses(pack_format::VER_41) {
+        upgrader.each(model, value, Model::Item, Upgrader::item_predicate);
+    }
+    if crosses(pack_format::VER_48) {
+        upgrader.each(model, value, Model::Predicate, Upgrader::looting);
+    }
+    if crosses(pack_format::VER_71) {
+        upgrader.each(model, value, Model::Text, Upgrader::text_events);
+    }
+    (file, upgrader.issues)
+}
+
+struct Upgrader {
+    file: String,
+    issues: Vec<UpgradeIssue>,
+}
+
+impl Upgrader {
+    fn issue(&mut self, location: String, message: impl Into<String>) {
+        self.issues.push(UpgradeIssue { file: self.file.clone(), location, message: message.into() });
+    }
+
+    /// Apply `f` to every JSON object of the `target` model in `value`, a resource of the `root`
+    /// model. Children are visited before their parents, so that `f` may move its fields around.
+    fn each(&mut self, root: Model, value: &mut Value, target: Model,
+            f: impl Fn(&mut Self, &mut Map<String, Value>, &str)) {
+        let mut nodes = Vec::new();
+        version::visit(root, value, &mut |node| if node.model == target {
+            nodes.push((node.pointer.to_string(), node.location.to_string()));
+        });
+        for (pointer, loc) in nodes.into_iter().rev() {
+            if let Some(Value::Object(obj)) = value.pointer_mut(&pointer) {
+                f(self, obj, &loc);
+            }
+        }
+    }
+
+    /// Wrap entity conditions in advancement criteria into lists of predicates.
+    fn criteria(&mut self, advancement: &mut Value) {
+        let criteria = match advancement.get_mut("criteria").and_then(Value::as_object_mut) {
+            Some(criteria) => criteria,
+            None => return,
+        };
+        for criterion in criteria.values_mut() {
+            let paths: Vec<String> = match serde_json::from_value::<Criterion>(criterion.clone()) {
+                Ok(parsed) => parsed.predicates_or_entities().into_iter()
+                    .filter(|(_, value)| matches!(value, Either::Right(_)))
+                    .map(|(path, _)| path)
+                    .collect(),
+                Err(_) => continue,
+            };
+            let conditions = match criterion.get_mut("conditions") {
+                Some(conditions) => conditions,
+                None => continue,
+            };
+            for path in paths {
+                if let Some(entity) = lookup_mut(conditions, &path) {
+                    let predicate = serde_json::json!({
+                        "condition": "minecraft:entity_properties",
+                        "entity": "this",
+                        "predicate": entity.take(),
+                    });
+                    *entity = Value::Array(vec![predicate]);
+                }
+            }
+        }
+    }
+
+    /// Move `fishing_hook`, `lightning_bolt` and `player` in entity conditions into
+    /// `type_specific`.
+    fn type_specific(&mut self, entity: &mut Map<String, Value>, loc: &str) {
+        let specific: Vec<_> = [("fishing_hook", "fishing_hook"), ("lightning_bolt", "lightning"), ("player", "player")]
+            .iter().filter(|(key, _)| entity.get(*key).is_some_and(Value::is_object)).collect();
+        match specific.as_slice() {
+            [] => {}
+            [(key, r#type)] if !entity.contains_key("type_specific") => {
+                let mut fields = Map::new();
+                fields.insert("type".to_string(), Value::from(*r#type));
+                if let Some(Value::Object(old)) = entity.shift_remove(*key) {
+                    fields.extend(old);
+                }
+                entity.insert("type_specific".to_string(), Value::Object(fields));
+            }
+            _ => self.issue(loc.to_string(), "only one of 'fishing_hook', 'lightning_bolt', 'player' and \
+                                              'type_specific' can be kept"),
+        }
+    }
+
+    /// Move fields of item conditions into `items` and `predicates`.
+    fn item_predicate(&mut self, item: &mut Map<String, Value>, loc: &str) {
+        if let Some(tag) = item.shift_remove("tag") {
+            match (item.get("items"), tag.as_str()) {
+                (None, Some(tag)) => { item.insert("items".to_string(), Value::from(format!("#{}", tag))); }
+                _ => {
+                    self.issue(join(loc, "tag"), "'tag' cannot be combined with 'items'");
+                    item.insert("tag".to_string(), tag);
+                }
+            }
+        }
+        let mut predicates = Map::new();
+        if let Some(nbt) = item.shift_remove("nbt") {
+            predicates.insert("minecraft:custom_data".to_string(), nbt);
+        }
+        if let Some(potion) = item.shift_remove("potion") {
+            predicates.insert("minecraft:potion_contents".to_string(), potion);
+        }
+        if let Some(durability) = item.shift_remove("durability") {
+            predicates.insert("minecraft:damage".to_string(), serde_json::json!({"durability": durability}));
+        }
+        for key in ["enchantments", "stored_enchantments"].iter() {
+            if let Some(mut enchantments) = item.shift_remove(*key) {
+                for enchantment in enchantments.as_array_mut().into_iter().flatten() {
+                    if let Some(enchantment) = enchantment.as_object_mut() {
+                        if let Some(id) = enchantment.shift_remove("enchantment") {
+                            enchantment.insert("enchantments".to_string(), id);
+                        }
+                    }
+                }
+                predicates.insert(format!("minecraft:{}", key), enchantments);
+            }
+        }
+        if predicates.is_empty() { return; }
+        match item.entry("predicates").or_insert_with(|| Value::Object(Map::new())) {
+            Value::Object(existing) => existing.extend(predicates),
+            _ => self.issue(join(loc, "predicates"), "'predicates' is not an object"),
+        }
+    }
+
+    /// Replace `random_chance_with_looting` by `random_chance_with_enchanted_bonus`.
+    fn looting(&mut self, obj: &mut Map<String, Value>, loc: &str) {
+        if !condition_is(obj, "random_chance_with_looting") { return; }
+        let chance = obj.get("chance").and_then(Value::as_f64);
+        let multiplier = obj.get("looting_multiplier").and_then(Value::as_f64);
+        let (chance, multiplier) = match (chance, multiplier) {
+            (Some(chance), Some(multiplier)) => (chance, multiplier),
+            _ => return self.issue(loc.to_string(), "'chance' and 'looting_multiplier' must be numbers"),
+        };
+        obj.clear();
+        obj.insert("condition".to_string(), Value::from("minecraft:random_chance_with_enchanted_bonus"));
+        obj.insert("enchantment".to_string(), Value::from("minecraft:looting"));
+        obj.insert("unenchanted_chance".to_string(), number(chance));
+        obj.insert("enchanted_chance".to_string(), serde_json::json!({
+            "type": "minecraft:linear",
+            "base": number(chance + multiplier),
+            "per_level_above_first": number(multiplier),
+        }));
+    }
+
+    /// Rename and reshape `clickEvent` and `hoverEvent` in text components.
+    fn text_events(&mut self, text: &mut Map<String, Value>, loc: &str) {
+        if let Some(mut event) = text.shift_remove("clickEvent") {
+            if let Some(event) = event.as_object_mut() {
+                let key = match event.get("action").and_then(Value::as_str) {
+                    Some("open_url") => Some("url"),
+                    Some("open_file") => Some("path"),
+                    Some("run_command") | Some("suggest_command") => Some("command"),
+                    Some("change_page") => Some("page"),
+                    _ => None,
+                };
+                if let (Some(key), Some(value)) = (key, event.shift_remove("value")) {
+                    let value = match (key, value.as_str().map(str::parse::<i64>)) {
+                        ("page", Some(Ok(page))) => Value::from(page),
+                        _ => value,
+                    };
+                    event.insert(key.to_string(), value);
+                }
+            }
+            text.insert("click_event".to_string(), event);
+        }
+        if let Some(mut event) = text.shift_remove("hoverEvent") {
+            if let Some(event) = event.as_object_mut() {
+                self.hover_event(event, &join(loc, "hover_event"));
+            }
+            text.insert("hover_event".to_string(), event);
+        }
+    }
+
+    fn hover_event(&mut self, event: &mut Map<String, Value>, loc: &str) {
+        let contents = match event.shift_remove("contents") {
+            Some(contents) => contents,
+            None => {
+                if event.contains_key("value") && event.get("action").and_then(Value::as_str) != Some("show_text") {
+                    self.issue(join(loc, "value"), "legacy 'value' cannot be converted, use 'contents' first");
+                }
+                return;
+            }
+        };
+        match (event.get("action").and_then(Value::as_str), contents) {
+            (Some("show_text"), contents) => { event.insert("value".to_string(), contents); }
+            (Some("show_item"), Value::String(id)) => { event.insert("id".to_string(), Value::String(id)); }
+            (Some("show_item"), Value::Object(item)) => event.extend(item),
+            (Some("show_entity"), Value::Object(mut entity)) => {
+                if let Some(uuid) = entity.shift_remove("id") {
+                    event.insert("uuid".to_string(), uuid);
+                }
+                if let Some(r#type) = entity.shift_remove("type") {
+                    event.insert("id".to_string(), r#type);
+                }
+                event.extend(entity);
+            }
+            (_, contents) => {
+                self.issue(join(loc, "contents"), "unrecognised hover event");
+                event.insert("contents".to_string(), contents);
+            }
+        }
+    }
+}
+
+/// Find a value by a path of keys and array indices, e.g. `victims[0]` or `damage.source_entity`.
+fn lookup_mut<'a>(mut value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    for segment in path.split('.') {
+        let (key, indices) = segment.split_once('[').map_or((segment, ""), |(k, rest)| (k, rest));
+        value = value.get_mut(key)?;
+        for index in indices.split('[').filter(|s| !s.is_empty()) {
+            value = value.get_mut(index.strip_suffix(']')?.parse::<usize>().ok()?)?;
+        }
+    }
+    Some(value)
+}
+
+fn condition_is(obj: &Map<String, Value>, condition: &str) -> bool {
+    obj.get("condition").and_then(Value::as_str)
+        .is_some_and(|c| c.strip_prefix("minecraft:").unwrap_or(c) == condition)
+}
+
+/// A JSON number, rounded to avoid artifacts of floating point arithmetic.
+fn number(x: f64) -> Value {
+    let rounded = (x * 1e9).round() / 1e9;
+    rounded.to_string().parse::<Number>().map_or(Value::Null, Value::Number)
+}
@@ -0,0 +1,283 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Cross-reference validation for whole [`DataPack`]s.
+//!
+//! A pack that parses fine can still refer to resources that do not exist, e.g. an advancement
+//! whose parent is missing, or a predicate referring to another predicate by a misspelled name.
+//!
+//! ```
+//! # use maplit::btreemap;
+//! # use minecraft_json::minecraft::data::pack::{DataPack, ResourceKind};
+//! # use minecraft_json::minecraft::data::meta::{McMeta, pack_format};
+//! # use minecraft_json::minecraft::data::advancement::{Advancement, Criterion, TriggerSpelling};
+//! # use minecraft_json::minecraft::data::validate::Validator;
+//! # use minecraft_json::minecraft::text::{TextComponent, StringLike};
+//! # use minecraft_json::minecraft::common::{Either, Extra};
+//! let mut pack = DataPack::new(McMeta::Pack {
+//!     description: TextComponent::RawTextLike(StringLike::String("demo".to_string())),
+//!     pack_format: pack_format::VER_7,
+//! });
+//! pack.advancements.insert("demo:sleep".to_string(), Advancement {
+//!     display: None,
+//!     parent: Some("minecraft:adventure/root".parse().unwrap()),
+//!     criteria: btreemap! {
+//!         "slept".to_string() => Criterion::SleptInBed {
+//!             location: None,
+//!             player: Some(Either::Left(vec![Either::Left("demo:is_night".parse().unwrap())])),
+//!             spelling: TriggerSpelling::Namespaced,
+//!             extra: Extra::default(),
+//!         },
+//!     },
+//!     requirements: Vec::new(),
+//!     rewards: None,
+//!     extra: Extra::default(),
+//! });
+//!
+//! let errors = Validator::new().validate(&pack);
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors[0].to_string(), "data/demo/advancements/sleep.json: parent: \
+//!                                    advancement 'minecraft:adventure/root' does not exist");
+//! assert_eq!(errors[1].location, "criteria.slept.conditions.player[0]");
+//!
+//! let validator = Validator::new()
+//!     .with_known(ResourceKind::Advancement, vec!["minecraft:adventure/root"]);
+//! assert_eq!(validator.validate(&pack).len(), 1);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use crate::minecraft::common::{Either, ResourceLocation};
+use crate::minecraft::data::pack::{DataPack, ResourceKind};
+use crate::minecraft::data::predicate::Predicate;
+use crate::minecraft::data::loot_table::Entry;
+use crate::minecraft::data::item_modifier::LootFunction;
+use crate::minecraft::data::tag::TagKind;
+use crate::minecraft::span::join;
+
+/// A reference to a resource that neither the pack nor the known IDs define.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct DanglingReference {
+    /// Path of the referring file relative to the pack root, see [`DataPack::source_of`].
+    pub file: String,
+    /// Location of the reference inside the file, as a dot-separated path of JSON keys, with
+    /// array indices in brackets.
+    pub location: String,
+    /// The kind of the referenced resource.
+    pub kind: ResourceKind,
+    /// The referenced ID.
+    pub id: String,
+}
+
+impl Display for DanglingReference {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}: {} '{}' does not exist", self.file, self.location, self.kind, self.id)
+    }
+}
+
+impl std::error::Error for DanglingReference {}
+
+/// Validator for cross references in a [`DataPack`].
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct Validator {
+    known: BTreeMap<ResourceKind, BTreeSet<String>>,
+}
+
+impl Validator {
+    /// Create a validator which considers only resources in the pack itself as known.
+    pub fn new() -> Validator { Validator::default() }
+
+    /// Treat some IDs as known even if the pack does not define them, typically the vanilla
+    /// `minecraft:` resources.
+    pub fn with_known<I, S>(mut self, kind: ResourceKind, ids: I) -> Validator
+        where I: IntoIterator<Item=S>, S: AsRef<str> {
+        self.add_known(kind, ids);
+        self
+    }
+
+    /// Treat some IDs as known even if the pack does not define them.
+    pub fn add_known<I, S>(&mut self, kind: ResourceKind, ids: I)
+        where I: IntoIterator<Item=S>, S: AsRef<str> {
+        self.known.entry(kind).or_default()
+            .extend(ids.into_iter().map(|id| ResourceLocation::normalise(id.as_ref())));
+    }
+
+    /// Report every dangling reference in a data pack.
+    pub fn validate(&self, pack: &DataPack) -> Vec<DanglingReference> {
+        let mut checker = Checker { validator: self, pack, file: String::new(), errors: Vec::new() };
+        checker.pack();
+        checker.errors
+    }
+}
+
+struct Checker<'a> {
+    validator: &'a Validator,
+    pack: &'a DataPack,
+    file: String,
+    errors: Vec<DanglingReference>,
+}
+
+impl Checker<'_> {
+    fn exists(&self, kind: ResourceKind, id: &str) -> bool {
+        let pack = self.pack;
+        let defined = match kind {
+            ResourceKind::Advancement => pack.advancements.contains_key(id),
+            ResourceKind::Predicate => pack.predicates.contains_key(id),
+            ResourceKind::LootTable => pack.loot_tables.contains_key(id),
+            ResourceKind::ItemModifier => pack.item_modifiers.contains_key(id),
+            ResourceKind::Recipe => pack.recipes.contains_key(id),
+            ResourceKind::Function => pack.functions.contains_key(id),
+            ResourceKind::Tag(tag_kind) => pack.tags.get(tag_kind).contains_tag(id),
+        };
+        defined || self.validator.known.get(&kind).is_some_and(|ids| ids.contains(id))
+    }
+
+    fn check(&mut self, kind: ResourceKind, id: &ResourceLocation, location: String) {
+        let id = id.to_string();
+        if !self.exists(kind, &id) {
+            self.errors.push(DanglingReference { file: self.file.clone(), location, kind, id });
+        }
+    }
+
+    fn pack(&mut self) {
+        let pack = self.pack;
+        for (id, advancement) in &pack.advancements {
+            self.file = pack.source_of(ResourceKind::Advancement, id);
+            if let Some(parent) = &advancement.parent {
+                self.check(ResourceKind::Advancement, parent, "parent".to_string());
+            }
+            for (name, criterion) in &advancement.criteria {
+                for (field, value) in criterion.predicates_or_entities() {
+                    if let Either::Left(predicates) = value {
+                        for (k, predicate) in predicates.iter().enumerate() {
+                            let loc = format!("criteria.{}.conditions.{}[{}]", name, field, k);
+                            match predicate {
+                                Either::Left(id) => self.check(ResourceKind::Predicate, id, loc),
+                                Either::Right(predicate) => self.predicate(predicate, loc),
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(rewards) = &advancement.rewards {
+                for (k, recipe) in rewards.recipes.iter().enumerate() {
+                    self.check(ResourceKind::Recipe, recipe, format!("rewards.recipes[{}]", k));
+                }
+                for (k, loot) in rewards.loot.iter().enumerate() {
+                    self.check(ResourceKind::LootTable, loot, format!("rewards.loot[{}]", k));
+                }
+                if let Some(function) = &rewards.function {
+                    self.check(ResourceKind::Function, function, "rewards.function".to_string());
+                }
+            }
+        }
+        for (id, predicate) in &pack.predicates {
+            self.file = pack.source_of(ResourceKind::Predicate, id);
+            match predicate {
+                Either::Left(predicate) => self.predicate(predicate, String::new()),
+                Either::Right(predicates) => self.predicates(predicates, ""),
+            }
+        }
+        for (id, table) in &pack.loot_tables {
+            self.file = pack.source_of(ResourceKind::LootTable, id);
+            self.functions(&table.functions, "functions");
+            for (k, pool) in table.pools.iter().enumerate() {
+                let loc = format!("pools[{}]", k);
+                self.predicates(&pool.conditions, &format!("{}.conditions", loc));
+                self.functions(&pool.functions, &format!("{}.functions", loc));
+                self.entries(&pool.entries, &format!("{}.entries", loc));
+            }
+        }
+        for (id, modifier) in &pack.item_modifiers {
+            self.file = pack.source_of(ResourceKind::ItemModifier, id);
+            match modifier {
+                Either::Left(function) => self.function(function, String::new()),
+                Either::Right(functions) => self.functions(functions, ""),
+            }
+        }
+        for &kind in TagKind::ALL.iter() {
+            for (id, tag) in pack.tags.get(kind).iter() {
+                self.file = pack.source_of(ResourceKind::Tag(kind), id);
+                for (k, entry) in tag.values.iter().enumerate() {
+                    if let (Some(nested), true) = (entry.referenced_tag(), entry.is_required()) {
+                        self.check(ResourceKind::Tag(kind), nested, format!("values[{}]", k));
+                    }
+                }
+            }
+        }
+    }
+
+    fn predicates(&mut self, predicates: &[Predicate], loc: &str) {
+        for (k, predicate) in predicates.iter().enumerate() {
+            self.predicate(predicate, format!("{}[{}]", loc, k));
+        }
+    }
+
+    fn predicate(&mut self, predicate: &Predicate, loc: String) {
+        match predicate {
+            Predicate::AllOf { terms, .. }
+            | Predicate::AnyOf { terms, .. }
+            | Predicate::Alternative { terms, .. } => self.predicates(terms, &join(&loc, "terms")),
+            Predicate::Inverted { term, .. } => self.predicate(term, join(&loc, "term")),
+            Predicate::Reference { name, .. } => self.check(ResourceKind::Predicate, name, join(&loc, "name")),
+            _ => {}
+        }
+    }
+
+    fn functions(&mut self, functions: &[LootFunction], loc: &str) {
+        for (k, function) in functions.iter().enumerate() {
+            self.function(function, format!("{}[{}]", loc, k));
+        }
+    }
+
+    fn function(&mut self, function: &LootFunction, loc: String) {
+        self.predicates(function.conditions(), &join(&loc, "conditions"));
+        match function {
+            LootFunction::SetContents { entries, .. } => self.entries(entries, &join(&loc, "entries")),
+            LootFunction::SetLootTable { name, .. } =>
+                self.check(ResourceKind::LootTable, name, join(&loc, "name")),
+            _ => {}
+        }
+    }
+
+    fn entries(&mut self, entries: &[Entry], loc: &str) {
+        for (k, entry) in entries.iter().enumerate() {
+            let loc = format!("{}[{}]", loc, k);
+            match entry {
+                Entry::Item { properties, .. }
+                | Entry::Tag { properties, .. }
+                | Entry::Dynamic { properties, .. }
+                | Entry::Empty { properties } => {
+                    self.predicates(&properties.conditions, &join(&loc, "conditions"));
+                    self.functions(&properties.functions, &join(&loc, "functions"));
+                }
+                Entry::LootTable { name, properties } => {
+                    self.check(ResourceKind::LootTable, name, join(&loc, "name"));
+                    self.predicates(&properties.conditions, &join(&loc, "conditions"));
+                    self.functions(&properties.functions, &join(&loc, "functions"));
+                }
+                Entry::Group { children, conditions }
+                | Entry::Alternatives { children, conditions }
+                | Entry::Sequence { children, conditions } => {
+                    self.predicates(conditions, &join(&loc, "conditions"));
+                    self.entries(children, &join(&loc, "children"));
+                }
+            }
+        }
+    }
+}
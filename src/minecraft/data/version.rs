@@ -0,0 +1,453 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Checking resources against the [`pack_format`] declared in `pack.mcmeta`.
+//!
+//! The models in this crate accept the union of all supported versions, so a field introduced in
+//! a later version (or removed in an earlier one) parses fine but is silently ignored by the game.
+//! These checks work on the JSON text, and report fields, condition types, trigger types and
+//! directory names not valid in the declared version. [`DataPack::load`] runs them on every file.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::meta::pack_format;
+//! # use minecraft_json::minecraft::data::version;
+//! let json = r#"{
+//!   "condition": "minecraft:any_of",
+//!   "terms": [{"condition": "entity_properties", "entity": "this",
+//!              "predicate": {"stepping_on": {"dimension": "overworld"}}}]
+//! }"#;
+//! let issues = version::check_file("data/demo/predicates/p.json", json, pack_format::VER_6);
+//! assert_eq!(issues.len(), 2);
+//! assert_eq!(issues[0].to_string(), "condition: condition type 'any_of' requires \
+//!                                    pack_format 15 or later, but the pack declares 6");
+//! assert_eq!(issues[1].location, "terms[0].predicate.stepping_on");
+//!
+//! let issues = version::check_file("data/demo/predicates/p.json", json, pack_format::VER_48);
+//! assert_eq!(issues[0].to_string(), "directory 'predicates' is not valid since pack_format 48, \
+//!                                    but the pack declares 48");
+//! assert_eq!(issues.len(), 1);
+//! ```
+//!
+//! Rules are anchored to the models they are about, so the same key elsewhere is not reported:
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::meta::pack_format;
+//! # use minecraft_json::minecraft::data::version;
+//! let json = r#"{"condition": "entity_scores", "entity": "this", "scores": {"components": 1}}"#;
+//! assert!(version::check_file("data/demo/predicates/p.json", json, pack_format::VER_7).is_empty());
+//!
+//! let json = r#"{"criteria": {"sleep": {"trigger": "slept_in_bed", "conditions": {
+//!   "player": [{"condition": "entity_properties", "entity": "this",
+//!               "predicate": {"player": {"level": {"min": 1}}}}]
+//! }}}}"#;
+//! let issues = version::check_file("data/demo/advancement/a.json", json, pack_format::VER_48);
+//! assert_eq!(issues.len(), 1);
+//! assert_eq!(issues[0].to_string(), "criteria.sleep.conditions.player[0].predicate.player: \
+//!                                    field 'player' is not valid since pack_format 10, \
+//!                                    but the pack declares 48");
+//! let issues = version::check_file("data/demo/advancements/a.json", json, pack_format::VER_5);
+//! assert_eq!(issues[0].to_string(), "criteria.sleep.conditions.player: list of predicates \
+//!                                    requires pack_format 6 or later, but the pack declares 5");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use serde_json::{Map, Value};
+use crate::minecraft::data::meta::pack_format;
+use crate::minecraft::data::pack::ResourceKind;
+use crate::minecraft::lenient;
+use crate::minecraft::span::join;
+
+#[cfg(doc)]
+use crate::minecraft::data::pack::DataPack;
+
+/// A feature used in a resource but not valid in the declared `pack_format`.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct VersionIssue {
+    /// Location of the feature, as a dot-separated path of JSON keys, with array indices in
+    /// brackets. Empty for directory names.
+    pub location: String,
+    /// Description of the feature, e.g. `field 'type_specific'`.
+    pub feature: String,
+    /// The first `pack_format` supporting the feature, if it is too new.
+    pub since: Option<i64>,
+    /// The first `pack_format` no longer supporting the feature, if it is too old.
+    pub until: Option<i64>,
+    /// The `pack_format` declared by the pack.
+    pub pack_format: i64,
+}
+
+impl Display for VersionIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if !self.location.is_empty() { write!(f, "{}: ", self.location)?; }
+        match (self.since, self.until) {
+            (Some(since), _) if self.pack_format < since =>
+                write!(f, "{} requires pack_format {} or later", self.feature, since)?,
+            (_, Some(until)) => write!(f, "{} is not valid since pack_format {}", self.feature, until)?,
+            _ => write!(f, "{} is not valid", self.feature)?,
+        }
+        write!(f, ", but the pack declares {}", self.pack_format)
+    }
+}
+
+impl std::error::Error for VersionIssue {}
+
+/// What a JSON object (or array) in a resource is, so that the rules apply only where the field
+/// has the meaning they are about. Lists of some model are visited as that model.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Model {
+    /// An [`Advancement`](crate::minecraft::data::advancement::Advancement).
+    Advancement,
+    /// The `display` of an advancement.
+    Display,
+    /// The `criteria` of an advancement, keyed by their names.
+    Criteria,
+    /// A [`Criterion`](crate::minecraft::data::advancement::Criterion).
+    Criterion,
+    /// The `conditions` of a criterion.
+    Conditions,
+    /// The `damage` in the `conditions` of a criterion.
+    Damage,
+    /// An entity condition, or a list of predicates since `pack_format` 6, see
+    /// [`PredicatesOrEntity`](crate::minecraft::data::conditions::PredicatesOrEntity).
+    PredicatesOrEntity,
+    /// A list of predicates in place of an entity condition.
+    Predicates,
+    /// A [`Predicate`](crate::minecraft::data::predicate::Predicate).
+    Predicate,
+    /// An [`Entity`](crate::minecraft::data::conditions::Entity) condition.
+    Entity,
+    /// The `equipment` of an entity condition.
+    Equipment,
+    /// A [`Player`](crate::minecraft::data::conditions::Player) condition.
+    Player,
+    /// A [`LightningBolt`](crate::minecraft::data::conditions::LightningBolt) condition.
+    LightningBolt,
+    /// A [`TypeSpecific`](crate::minecraft::data::conditions::TypeSpecific) entity condition.
+    TypeSpecific,
+    /// A [`DamageSource`](crate::minecraft::data::conditions::DamageSource) condition.
+    DamageSource,
+    /// An [`Item`](crate::minecraft::data::conditions::Item) condition.
+    Item,
+    /// A [`LootTable`](crate::minecraft::data::loot_table::LootTable).
+    LootTable,
+    /// A pool of a loot table.
+    Pool,
+    /// An entry of a loot pool.
+    Entry,
+    /// A [`LootFunction`](crate::minecraft::data::item_modifier::LootFunction).
+    Function,
+    /// A [`TextComponent`](crate::minecraft::text::TextComponent).
+    Text,
+    /// A [`HoverEvent`](crate::minecraft::text::HoverEvent).
+    HoverEvent,
+    /// The entity shown by a hover event.
+    HoverEntity,
+    /// The contents of `pack.mcmeta`.
+    McMeta,
+    /// The `pack` section of `pack.mcmeta`.
+    PackSection,
+}
+
+/// Fields of criterion conditions holding entity conditions, see
+/// [`Criterion::predicates_or_entities`](crate::minecraft::data::advancement::Criterion::predicates_or_entities).
+const ENTITY_SLOTS: &[&str] = &[
+    "bystander", "child", "entity", "lightning", "parent", "partner", "player", "projectile",
+    "source", "villager", "zombie",
+];
+
+impl Model {
+    /// The model of a resource file.
+    pub(crate) fn of(kind: ResourceKind) -> Option<Model> {
+        match kind {
+            ResourceKind::Advancement => Some(Model::Advancement),
+            ResourceKind::Predicate => Some(Model::Predicate),
+            ResourceKind::LootTable => Some(Model::LootTable),
+            ResourceKind::ItemModifier => Some(Model::Function),
+            _ => None,
+        }
+    }
+
+    /// The model of the field `key` in `obj`, an object of this model.
+    fn field(self, obj: &Map<String, Value>, key: &str) -> Option<Model> {
+        let id = |key| obj.get(key).and_then(Value::as_str).map(|id| id.strip_prefix("minecraft:").unwrap_or(id));
+        Some(match (self, key) {
+            (Model::Advancement, "display") => Model::Display,
+            (Model::Advancement, "criteria") => Model::Criteria,
+            (Model::Display, "title") | (Model::Display, "description") => Model::Text,
+            (Model::Criteria, _) => Model::Criterion,
+            (Model::Criterion, "conditions") => Model::Conditions,
+            (Model::Conditions, "victims") => Model::PredicatesOrEntity,
+            (Model::Conditions, key) if ENTITY_SLOTS.contains(&key) => Model::PredicatesOrEntity,
+            (Model::Conditions, "damage") => Model::Damage,
+            (Model::Conditions, "item") | (Model::Conditions, "items") | (Model::Conditions, "rod") => Model::Item,
+            (Model::Damage, "source_entity") => Model::PredicatesOrEntity,
+            (Model::Damage, "type") => Model::DamageSource,
+            (Model::Predicate, "term") | (Model::Predicate, "terms") => Model::Predicate,
+            (Model::Predicate, "predicate") => match id("condition")? {
+                "entity_properties" => Model::Entity,
+                "damage_source_properties" => Model::DamageSource,
+                "match_tool" => Model::Item,
+                _ => return None,
+            },
+            (Model::DamageSource, "direct_entity") | (Model::DamageSource, "source_entity") => Model::Entity,
+            (Model::Entity, "passenger") | (Model::Entity, "targeted_entity") | (Model::Entity, "vehicle") =>
+                Model::Entity,
+            (Model::Entity, "equipment") => Model::Equipment,
+            (Model::Entity, "player") => Model::Player,
+            (Model::Entity, "lightning_bolt") => Model::LightningBolt,
+            (Model::Entity, "type_specific") => Model::TypeSpecific,
+            (Model::Equipment, _) => Model::Item,
+            (Model::Player, "looking_at") => Model::Entity,
+            (Model::LightningBolt, "entity_struck") => Model::Entity,
+            // the lightning bolt as an entity is flattened
+            (Model::LightningBolt, key) => return Model::Entity.field(obj, key),
+            (Model::TypeSpecific, key) => match id("type")? {
+                "player" => return Model::Player.field(obj, key),
+                "lightning" if key == "entity_struck" => Model::Entity,
+                _ => return None,
+            },
+            (Model::LootTable, "pools") => Model::Pool,
+            (Model::LootTable, "functions") | (Model::Pool, "functions") | (Model::Entry, "functions") =>
+                Model::Function,
+            (Model::Pool, "conditions") | (Model::Entry, "conditions") | (Model::Function, "conditions") =>
+                Model::Predicate,
+            (Model::Pool, "entries") | (Model::Entry, "children") => Model::Entry,
+            (Model::Function, key) => match (id("function")?, key) {
+                ("set_name", "name") | ("set_lore", "lore") => Model::Text,
+                ("sequence", "functions") | ("filtered", "modifier") => Model::Function,
+                ("filtered", "item_filter") => Model::Item,
+                _ => return None,
+            },
+            (Model::Text, "extra") | (Model::Text, "with") | (Model::Text, "separator") => Model::Text,
+            (Model::Text, "hoverEvent") | (Model::Text, "hover_event") => Model::HoverEvent,
+            (Model::HoverEvent, key) => match (id("action")?, key) {
+                ("show_text", "contents") | ("show_text", "value") | ("show_entity", "name") => Model::Text,
+                ("show_entity", "contents") => Model::HoverEntity,
+                _ => return None,
+            },
+            (Model::HoverEntity, "name") => Model::Text,
+            (Model::McMeta, "pack") => Model::PackSection,
+            (Model::PackSection, "description") => Model::Text,
+            _ => return None,
+        })
+    }
+}
+
+/// A JSON object or array in a resource, as found by [`visit`].
+pub(crate) struct Node<'a> {
+    /// What the value is.
+    pub model: Model,
+    /// The value, an object, or a list of predicates for [`Model::Predicates`].
+    pub value: &'a Value,
+    /// Location of the value, as a dot-separated path of JSON keys, with array indices in
+    /// brackets.
+    pub location: &'a str,
+    /// Location of the value, as a JSON pointer, see [`Value::pointer`].
+    pub pointer: &'a str,
+}
+
+/// Visit all JSON objects of known models in `value`, parents before children.
+pub(crate) fn visit(model: Model, value: &Value, f: &mut impl FnMut(Node)) {
+    visit_at(model, value, String::new(), String::new(), f)
+}
+
+fn visit_at(model: Model, value: &Value, loc: String, pointer: String, f: &mut impl FnMut(Node)) {
+    match value {
+        Value::Object(obj) => {
+            let model = if model == Model::PredicatesOrEntity { Model::Entity } else { model };
+            f(Node { model, value, location: &loc, pointer: &pointer });
+            for (key, value) in obj {
+                if let Some(field) = model.field(obj, key) {
+                    let escaped = key.replace('~', "~0").replace('/', "~1");
+                    visit_at(field, value, join(&loc, key), format!("{}/{}", pointer, escaped), f);
+                }
+            }
+        }
+        Value::Array(values) => {
+            let model = match model {
+                // a list of predicates, unless nested in a list, e.g. in `victims`
+                Model::PredicatesOrEntity if values.iter().all(|v| !v.is_array()) => {
+                    f(Node { model: Model::Predicates, value, location: &loc, pointer: &pointer });
+                    Model::Predicate
+                }
+                model => model,
+            };
+            for (k, value) in values.iter().enumerate() {
+                visit_at(model, value, format!("{}[{}]", loc, k), format!("{}/{}", pointer, k), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// What a [`Rule`] is about, in objects of its model.
+enum Subject {
+    /// A field.
+    Field(&'static str),
+    /// A value of a field, usually an ID with an optional `minecraft:` namespace.
+    Value(&'static str, &'static str),
+    /// The object (or array) itself.
+    Node,
+}
+
+/// A field, or a value of a field, of some model valid only in a range of versions.
+struct Rule {
+    model: Model,
+    subject: Subject,
+    feature: &'static str,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+const fn field(model: Model, key: &'static str, since: Option<i64>, until: Option<i64>) -> Rule {
+    Rule { model, subject: Subject::Field(key), feature: "field", since, until }
+}
+
+const fn condition(value: &'static str, since: Option<i64>, until: Option<i64>) -> Rule {
+    let subject = Subject::Value("condition", value);
+    Rule { model: Model::Predicate, subject, feature: "condition type", since, until }
+}
+
+const fn trigger(value: &'static str, since: Option<i64>, until: Option<i64>) -> Rule {
+    let subject = Subject::Value("trigger", value);
+    Rule { model: Model::Criterion, subject, feature: "trigger", since, until }
+}
+
+const RULES: &[Rule] = &[
+    // entity conditions in advancement criteria
+    Rule {
+        model: Model::Predicates, subject: Subject::Node, feature: "list of predicates",
+        since: Some(pack_format::VER_6), until: None,
+    },
+    // entity predicates
+    field(Model::Entity, "stepping_on", Some(pack_format::VER_7), None),
+    field(Model::Entity, "type_specific", Some(pack_format::VER_10), None),
+    field(Model::Entity, "fishing_hook", None, Some(pack_format::VER_10)),
+    field(Model::Entity, "lightning_bolt", None, Some(pack_format::VER_10)),
+    field(Model::Entity, "player", None, Some(pack_format::VER_10)),
+    // item predicates
+    field(Model::Item, "components", Some(pack_format::VER_41), None),
+    field(Model::Item, "predicates", Some(pack_format::VER_41), None),
+    field(Model::Item, "tag", None, Some(pack_format::VER_41)),
+    field(Model::Item, "nbt", None, Some(pack_format::VER_41)),
+    field(Model::Item, "potion", None, Some(pack_format::VER_41)),
+    field(Model::Item, "durability", None, Some(pack_format::VER_41)),
+    field(Model::Item, "enchantments", None, Some(pack_format::VER_41)),
+    field(Model::Item, "stored_enchantments", None, Some(pack_format::VER_41)),
+    // text components
+    field(Model::Text, "click_event", Some(pack_format::VER_71), None),
+    field(Model::Text, "hover_event", Some(pack_format::VER_71), None),
+    field(Model::Text, "clickEvent", None, Some(pack_format::VER_71)),
+    field(Model::Text, "hoverEvent", None, Some(pack_format::VER_71)),
+    // predicates
+    condition("all_of", Some(pack_format::VER_15), None),
+    condition("any_of", Some(pack_format::VER_15), None),
+    condition("alternative", None, Some(pack_format::VER_15)),
+    condition("enchantment_active_check", Some(pack_format::VER_48), None),
+    condition("random_chance_with_enchanted_bonus", Some(pack_format::VER_48), None),
+    condition("random_chance_with_looting", None, Some(pack_format::VER_48)),
+    // advancement triggers
+    trigger("allay_drop_item_on_block", Some(pack_format::VER_10), None),
+    trigger("avoid_vibration", Some(pack_format::VER_10), None),
+    trigger("kill_mob_near_sculk_catalyst", Some(pack_format::VER_10), None),
+    trigger("ride_entity_in_lava", Some(pack_format::VER_10), None),
+    trigger("thrown_item_picked_up_by_player", Some(pack_format::VER_10), None),
+    trigger("recipe_crafted", Some(pack_format::VER_15), None),
+    trigger("fall_after_explosion", Some(pack_format::VER_26), None),
+];
+
+impl Rule {
+    fn is_valid(&self, pack_format: i64) -> bool {
+        self.since.is_none_or(|since| pack_format >= since)
+            && self.until.is_none_or(|until| pack_format < until)
+    }
+
+    /// Where this rule is violated in `node`, if at all, as a key in the node.
+    fn matches(&self, node: &Node) -> Option<Option<&'static str>> {
+        if node.model != self.model { return None; }
+        match self.subject {
+            Subject::Field(key) => node.value.get(key).map(|_| Some(key)),
+            Subject::Value(key, expected) => node.value.get(key).and_then(Value::as_str)
+                .filter(|v| v.strip_prefix("minecraft:").unwrap_or(v) == expected)
+                .map(|_| Some(key)),
+            Subject::Node => Some(None),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.subject {
+            Subject::Field(key) => format!("{} '{}'", self.feature, key),
+            Subject::Value(_, value) => format!("{} '{}'", self.feature, value),
+            Subject::Node => self.feature.to_string(),
+        }
+    }
+}
+
+/// Check a JSON resource of some kind against a `pack_format`.
+pub fn check_value(kind: ResourceKind, value: &Value, pack_format: i64) -> Vec<VersionIssue> {
+    let mut issues = Vec::new();
+    let model = match Model::of(kind) {
+        Some(model) => model,
+        None => return issues,
+    };
+    visit(model, value, &mut |node| {
+        for rule in RULES.iter().filter(|r| !r.is_valid(pack_format)) {
+            if let Some(key) = rule.matches(&node) {
+                issues.push(VersionIssue {
+                    location: key.map_or(node.location.to_string(), |key| join(node.location, key)),
+                    feature: rule.describe(),
+                    since: rule.since,
+                    until: rule.until,
+                    pack_format,
+                });
+            }
+        }
+    });
+    issues
+}
+
+/// Check the directory name of a resource file, given its path relative to the pack root,
+/// against a `pack_format`.
+pub fn check_path(path: &str, pack_format: i64) -> Option<VersionIssue> {
+    let (kind, id) = ResourceKind::from_path(path)?;
+    if path == kind.path_for(&id, pack_format) {
+        return None;
+    }
+    let (old, new) = (kind.directory_for(pack_format::VER_7), kind.directory_for(pack_format::VER_48));
+    let singular = path.contains(&format!("/{}/", new));
+    Some(VersionIssue {
+        location: String::new(),
+        feature: format!("directory '{}'", if singular { new } else { old }),
+        since: if singular { Some(pack_format::VER_48) } else { None },
+        until: if singular { None } else { Some(pack_format::VER_48) },
+        pack_format,
+    })
+}
+
+/// Check a resource file, given its path relative to the pack root, against a `pack_format`.
+/// The directory name is always checked, see [`check_path`]; the contents are checked only for
+/// JSON resources that parse successfully, see [`check_value`].
+pub fn check_file(path: &str, contents: &str, pack_format: i64) -> Vec<VersionIssue> {
+    let mut issues: Vec<_> = check_path(path, pack_format).into_iter().collect();
+    if let Some((kind, _)) = ResourceKind::from_path(path).filter(|(kind, _)| kind.extension() == "json") {
+        if let Ok(parsed) = lenient::parse(contents) {
+            issues.extend(check_value(kind, &parsed.value, pack_format));
+        }
+    }
+    issues
+}
@@ -0,0 +1,386 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Deserialisation errors with the location of the offending value.
+//!
+//! Plain `serde_json` errors only carry a line and a column, and untagged unions (e.g.
+//! [`TextComponent`], [`Either`], [`Ranged2`]) report "data did not match any variant" without
+//! saying why. [`from_str`] reports the path of JSON keys leading to the error, and for untagged
+//! unions the error of the variant that got furthest.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::advancement::Advancement;
+//! # use minecraft_json::minecraft::error;
+//! let json = r#"{
+//!   "criteria": {
+//!     "killed": {
+//!       "trigger": "minecraft:player_killed_entity",
+//!       "conditions": {
+//!         "entity": {"location": {"block": {"blocks": ["minecraft:Stone"]}}}
+//!       }
+//!     }
+//!   }
+//! }"#;
+//! let err = error::from_str::<Advancement>(json).unwrap_err();
+//! assert_eq!(err.path, "criteria.killed.conditions.entity.location.block.blocks[0]");
+//! assert_eq!(err.message, "invalid character 'S' at position 10 in resource location 'minecraft:Stone'");
+//! assert_eq!((err.line, err.column), (6, 54));
+//! ```
+//!
+//! Values buffered by `serde` lose their location (e.g. the fields of internally tagged enums, or
+//! flattened fields). The error is then located at the first value in the text it fits:
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::predicate::Predicate;
+//! # use minecraft_json::minecraft::error;
+//! let json = r#"{"condition": "minecraft:match_tool", "predicate": {"items": ["minecraft:stick", true]}}"#;
+//! let err = error::from_str::<Predicate>(json).unwrap_err();
+//! assert_eq!(err.path, "predicate.items[1]");
+//! assert_eq!(err.message, "invalid type: boolean `true`, expected a string");
+//! assert_eq!((err.line, err.column), (1, 82));
+//! ```
+
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use serde::de::{DeserializeOwned, Deserializer, Error as _, Unexpected};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use serde_path_to_error::{Path, Segment, Track};
+use crate::minecraft::lenient::SyntaxError;
+use crate::minecraft::span::{self, join};
+
+#[cfg(doc)]
+use crate::minecraft::{common::{Either, Extra, Ranged2}, text::TextComponent};
+
+/// A deserialisation error, with the location of the offending value.
+#[derive(Debug)]
+pub struct ParseError {
+    /// Location of the error, as a dot-separated path of JSON keys, with array indices in
+    /// brackets. Empty if the error is at the top level, or in the JSON syntax.
+    pub path: String,
+    /// Line of the offending value (or of the syntax error), starting from 1; 0 if unknown.
+    pub line: usize,
+    /// Column of the offending value (or of the syntax error), starting from 1; 0 if unknown.
+    pub column: usize,
+    /// The error message, without the location.
+    pub message: String,
+    error: serde_json::Error,
+}
+
+impl ParseError {
+    /// The underlying `serde_json` error.
+    pub fn json_error(&self) -> &serde_json::Error { &self.error }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if !self.path.is_empty() { write!(f, "{}: ", self.path)?; }
+        write!(f, "{}", self.message)?;
+        if self.line != 0 { write!(f, " at line {} column {}", self.line, self.column)?; }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> { Some(&self.error) }
+}
+
+/// Deserialise an instance of `T` from JSON text, reporting the location of errors. The line and
+/// column are those of the offending value.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, ParseError> {
+    let mut track = Track::new();
+    let mut de = serde_json::Deserializer::from_str(s);
+    let result = T::deserialize(serde_path_to_error::Deserializer::new(&mut de, &mut track))
+        .and_then(|value| de.end().map(|()| value));
+    result.map_err(|error| {
+        // the text is valid JSON, so locate the error again using the spans of the values
+        if error.is_data() {
+            if let Err(err) = span::from_str::<T>(s) { return err; }
+        }
+        located(error, track, None)
+    })
+}
+
+/// Deserialise an instance of `T` from a JSON value, reporting the location of errors. The line
+/// and column are unknown.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, ParseError> {
+    let mut track = Track::new();
+    T::deserialize(serde_path_to_error::Deserializer::new(&value, &mut track))
+        .map_err(|error| located(error, track, Some(&value)))
+}
+
+fn located(error: serde_json::Error, track: Track, value: Option<&Value>) -> ParseError {
+    let failure = locate(value, &track.path(), Failure::parse(strip_position(&error.to_string())));
+    ParseError { path: failure.path, line: error.line(), column: error.column(), message: failure.message, error }
+}
+
+impl From<SyntaxError> for ParseError {
+    fn from(err: SyntaxError) -> Self {
+        let (line, column, message) = (err.line, err.column, err.message.clone());
+        ParseError { path: String::new(), line, column, message, error: serde_json::Error::custom(err) }
+    }
+}
+
+thread_local! {
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` in strict mode: unknown fields (see [`Extra`]) are rejected as errors rather than
+/// kept. Applies to all deserialisation on the current thread while `f` runs, e.g. to
+/// [`from_str`], or to `DataPack::load`.
+pub fn strict<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) { STRICT.with(|strict| strict.set(self.0)) }
+    }
+    let _restore = Restore(STRICT.with(|strict| strict.replace(true)));
+    f()
+}
+
+/// Are we in [`strict`] mode?
+pub fn is_strict() -> bool { STRICT.with(Cell::get) }
+
+/// Error for an unknown field in [`strict`] mode, located at the field.
+pub(crate) fn unknown_field<E: serde::de::Error>(key: &str) -> E {
+    fail(Failure::new(key.to_string(), "unknown field".to_string()))
+}
+
+/// Failure of an untagged union, relative to the union itself. Failures are carried across
+/// deserialisers in the error message, see [`fail`] and [`Failure::parse`].
+#[derive(Debug, Clone)]
+pub(crate) struct Failure {
+    path: String,
+    message: String,
+    /// The variant does not accept this kind of JSON value at all, e.g. an object for a number.
+    mismatch: bool,
+}
+
+impl Failure {
+    fn new(path: String, message: String) -> Failure {
+        Failure { path, message, mismatch: false }
+    }
+
+    /// Read back a failure from the message of an error, as written by its [`Display`] impl.
+    fn parse(message: &str) -> Failure {
+        match message.strip_suffix('`').and_then(|message| message.rsplit_once(" at `")) {
+            Some((message, path)) => Failure::new(path.to_string(), message.to_string()),
+            None => Failure::new(String::new(), message.to_string()),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.path.is_empty() { write!(f, " at `{}`", self.path)?; }
+        Ok(())
+    }
+}
+
+/// An attempt to deserialise one variant of an untagged union, see [`variant`] and [`nested`].
+pub(crate) type Attempt<'a, T> = &'a dyn Fn(&Value) -> Result<T, Failure>;
+
+/// Attempt to deserialise a variant holding `V`.
+pub(crate) fn variant<V: DeserializeOwned, T>(value: &Value, wrap: impl FnOnce(V) -> T) -> Result<T, Failure> {
+    // whether `V` takes objects (arrays) at all is decided by whether it takes an empty one
+    let empty = match value {
+        Value::Object(_) => Some(Value::Object(Map::new())),
+        Value::Array(_) => Some(Value::Array(Vec::new())),
+        _ => None,
+    };
+    if let Some(Err(error)) = empty.as_ref().map(V::deserialize) {
+        if is_invalid_type(&error.to_string()) {
+            return Err(Failure { mismatch: true, ..Failure::new(String::new(), error.to_string()) });
+        }
+    }
+    tracked(value, Some(value), |d| V::deserialize(d)).map(wrap).map_err(|mut failure| {
+        failure.mismatch = empty.is_none() && failure.path.is_empty() && is_invalid_type(&failure.message);
+        failure
+    })
+}
+
+/// Deserialise with a separate deserialiser (e.g. after buffering the input, or with a function
+/// generated by `#[serde(remote = "Self")]`), keeping the location of errors inside it.
+pub(crate) fn nested<'de, D, T, E>(deserializer: D, f: impl FnOnce(Tracked<'_, '_, D>) -> Result<T, D::Error>)
+                                   -> Result<T, E>
+    where D: Deserializer<'de>, E: serde::de::Error {
+    tracked(deserializer, None, f).map_err(fail)
+}
+
+/// Same as [`nested`], deserialising from a buffered value. Errors inside the value can then be
+/// located even if the path to the value is lost.
+pub(crate) fn nested_value<T, E: serde::de::Error>(
+    value: Value, f: impl FnOnce(Tracked<'_, '_, &Value>) -> serde_json::Result<T>) -> Result<T, E> {
+    tracked(&value, Some(&value), f).map_err(fail)
+}
+
+/// For `#[serde(flatten, deserialize_with = "error::flattened")]`: flattened fields are
+/// deserialised from buffered entries, losing the location of errors unless [`nested`].
+pub(crate) fn flattened<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<T, D::Error> {
+    nested(deserializer, |d| T::deserialize(d))
+}
+
+/// Report `failure` as an error of any deserialiser, to be read back with [`Failure::parse`].
+fn fail<E: serde::de::Error>(failure: Failure) -> E { E::custom(failure) }
+
+/// A deserialiser keeping track of the current path.
+pub(crate) type Tracked<'a, 'b, D> = serde_path_to_error::Deserializer<'a, 'b, D>;
+
+/// Deserialise with `f`, from `value` if it is known.
+fn tracked<'de, D, T>(deserializer: D, value: Option<&Value>,
+                      f: impl FnOnce(Tracked<'_, '_, D>) -> Result<T, D::Error>) -> Result<T, Failure>
+    where D: Deserializer<'de> {
+    let mut track = Track::new();
+    f(serde_path_to_error::Deserializer::new(deserializer, &mut track))
+        .map_err(|error| locate(value, &track.path(), Failure::parse(strip_position(&error.to_string()))))
+}
+
+/// Deserialise an untagged union by trying each variant in order. On failure, report the error
+/// of the variant that got deepest into the value. Variants not accepting this kind of value are
+/// ignored, and if several variants fail right at the value, a generic error is reported.
+pub(crate) fn untagged<'de, D: Deserializer<'de>, T>(deserializer: D, name: &str, attempts: &[Attempt<T>])
+                                                     -> Result<T, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    let mut best: Option<Failure> = None;
+    let mut at_top = 0;
+    for attempt in attempts {
+        match attempt(&value) {
+            Ok(result) => return Ok(result),
+            Err(failure) if failure.mismatch => {}
+            Err(failure) => {
+                at_top += failure.path.is_empty() as usize;
+                if best.as_ref().is_none_or(|best| depth(&failure.path) > depth(&best.path)) {
+                    best = Some(failure);
+                }
+            }
+        }
+    }
+    let failure = match best {
+        Some(best) if !best.path.is_empty() || at_top == 1 => best,
+        Some(_) => Failure::new(String::new(), format!("data did not match any variant of untagged enum {}", name)),
+        None => {
+            let error = serde_json::Error::invalid_type(unexpected(&value), &name);
+            Failure { mismatch: true, ..Failure::new(String::new(), error.to_string()) }
+        }
+    };
+    Err(fail(failure))
+}
+
+fn depth(path: &str) -> usize {
+    if path.is_empty() { 0 } else { path.matches(['.', '[']).count() + 1 }
+}
+
+fn is_invalid_type(message: &str) -> bool { message.starts_with("invalid type: ") }
+
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Null => Unexpected::Unit,
+        Value::Bool(b) => Unexpected::Bool(*b),
+        Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(n), _, _) => Unexpected::Unsigned(n),
+            (_, Some(n), _) => Unexpected::Signed(n),
+            (_, _, Some(n)) => Unexpected::Float(n),
+            _ => Unexpected::Other("number"),
+        },
+        Value::String(s) => Unexpected::Str(s),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Object(_) => Unexpected::Map,
+    }
+}
+
+/// Locate the failure `inner` of a deserialiser that was `track`ed from `value`. Segments of the
+/// path lost in the deserialiser (e.g. in internally tagged enums, or flattened fields) are
+/// recovered by looking for the first value in the text the failure fits, see [`fits`].
+fn locate(value: Option<&Value>, track: &Path, inner: Failure) -> Failure {
+    let prefix = render(track.iter());
+    let node = value.and_then(|value| track.iter().try_fold(value, |node, segment| match segment {
+        Segment::Seq { index } => node.get(index),
+        Segment::Map { key } | Segment::Enum { variant: key } => node.get(key),
+        Segment::Unknown => None,
+    }));
+    let found = node.and_then(|node| find(node, &inner));
+    Failure::new(join(&join(&prefix, &found.unwrap_or_default()), &inner.path), inner.message)
+}
+
+/// Path of the first node in `node` (itself included) the failure fits.
+fn find(node: &Value, failure: &Failure) -> Option<String> {
+    if fits(node, failure) { return Some(String::new()); }
+    match node {
+        Value::Array(items) => items.iter().enumerate()
+            .find_map(|(k, item)| Some(join(&format!("[{}]", k), &find(item, failure)?))),
+        Value::Object(obj) => obj.iter().find_map(|(key, item)| Some(join(key, &find(item, failure)?))),
+        _ => None,
+    }
+}
+
+/// Could the failure have happened in `node`? The path of the failure must lead to a value, and
+/// that value must be the one named in the message, if any (e.g. in "invalid type: ...").
+fn fits(node: &Value, failure: &Failure) -> bool {
+    let target = lookup(node, &failure.path);
+    let message = &failure.message;
+    let named = ["invalid type: ", "invalid value: "].iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .and_then(|rest| rest.split_once(", expected ").map(|(named, _)| named.to_string()))
+        .or_else(|| message.strip_prefix("unknown variant `")
+            .and_then(|rest| rest.split_once('`'))
+            .map(|(variant, _)| Unexpected::Str(variant).to_string()));
+    match (target, named) {
+        // numbers in values are named "number", and only those buffered by `serde` are exact
+        (Some(target), Some(named)) => unexpected(target).to_string() == named || (target.is_number() && named == "number"),
+        (target, None) => target.is_some(),
+        (None, Some(_)) => false,
+    }
+}
+
+/// The value at `path` inside `node`.
+fn lookup<'a>(node: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() { return Some(node); }
+    let (head, rest) = match path.find(['.', '[']) {
+        Some(0) => {
+            let close = path.find(']')?;
+            let index = path[1..close].parse::<usize>().ok()?;
+            return lookup(node.get(index)?, path[close + 1..].trim_start_matches('.'));
+        }
+        Some(k) => (&path[..k], path[k..].trim_start_matches('.')),
+        None => (path, ""),
+    };
+    lookup(node.get(head)?, rest)
+}
+
+fn render<'a>(segments: impl Iterator<Item=&'a Segment>) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Seq { index } => path += &format!("[{}]", index),
+            Segment::Map { key } | Segment::Enum { variant: key } => path = join(&path, key),
+            Segment::Unknown => path = join(&path, "?"),
+        }
+    }
+    path
+}
+
+/// Remove the trailing " at line L column C" from a `serde_json` error message.
+fn strip_position(message: &str) -> &str {
+    let stripped = message.rfind(" at line ").map(|k| (&message[..k], &message[k + 9..]));
+    match stripped {
+        Some((stripped, position)) if position.split(" column ").count() == 2
+            && position.split(" column ").all(|n| !n.is_empty() && n.bytes().all(|c| c.is_ascii_digit())) =>
+            stripped,
+        _ => message,
+    }
+}
@@ -0,0 +1,389 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A JSON reader accepting the same syntax as the game.
+//!
+//! The game reads JSON with Gson in lenient mode, which accepts, on top of standard JSON:
+//!
+//! - comments: `// ...`, `# ...` and `/* ... */`;
+//! - single-quoted strings, and unquoted strings and keys (e.g. `{type: crafting_shaped}`);
+//! - `=` and `=>` between keys and values, and `;` between elements;
+//! - empty array elements (e.g. `[1,,2]` and `[1,]`), which are taken to be `null`;
+//! - `true`, `false` and `null` in any case, and the `)]}'` prefix.
+//!
+//! Unquoted values that are not valid JSON numbers (e.g. `012` or `1.5f`) are strings. Trailing
+//! commas in objects are still rejected. For duplicate keys the last value wins, as in the game,
+//! but they are reported, since this is hardly ever intended.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::advancement::Advancement;
+//! # use minecraft_json::minecraft::lenient;
+//! let json = r#"{
+//!   // comments, unquoted and single-quoted strings
+//!   criteria: {tick: {trigger: 'minecraft:tick'}},
+//!   requirements: [[tick]; [tick]],
+//!   "requirements" = [[tick]]
+//! }"#;
+//! let parsed = lenient::parse(json).unwrap();
+//! assert_eq!(parsed.value["criteria"]["tick"]["trigger"], "minecraft:tick");
+//! assert_eq!(parsed.duplicates[0].to_string(), "duplicate key 'requirements' at line 5 column 3");
+//!
+//! let advancement: Advancement = lenient::from_str(json).unwrap();
+//! assert_eq!(advancement.requirements, vec![vec!["tick".to_string()]]);
+//!
+//! assert_eq!(lenient::parse("[1,,2,]").unwrap().value, serde_json::json!([1, null, 2, null]));
+//! assert_eq!(lenient::parse("{a: 1,}").unwrap_err().to_string(), "expected a name at line 1 column 7");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use crate::minecraft::error::ParseError;
+use crate::minecraft::span::{self, join, Span, Spans};
+
+#[cfg(doc)]
+use crate::minecraft::error;
+
+/// Result of [`parse`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Parsed {
+    /// The JSON value.
+    pub value: Value,
+    /// Keys appearing more than once in an object, except for their first occurrence.
+    pub duplicates: Vec<DuplicateKey>,
+    /// Spans of all values and keys.
+    pub spans: Spans,
+}
+
+/// A key appearing more than once in an object. The last value is kept.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct DuplicateKey {
+    /// Location of the key, as a dot-separated path of JSON keys, with array indices in
+    /// brackets. The last component is the key itself.
+    pub path: String,
+    /// The key itself.
+    pub key: String,
+    /// Line of the key, starting from 1.
+    pub line: usize,
+    /// Column of the key, starting from 1.
+    pub column: usize,
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "duplicate key '{}' at line {} column {}", self.key, self.line, self.column)
+    }
+}
+
+impl std::error::Error for DuplicateKey {}
+
+/// Malformed JSON, even for the lenient reader.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct SyntaxError {
+    /// The error message, without the location.
+    pub message: String,
+    /// Line where the error was detected, starting from 1.
+    pub line: usize,
+    /// Column where the error was detected, starting from 1.
+    pub column: usize,
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} at line {} column {}", self.message, self.line, self.column)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Parse JSON text as the game does.
+pub fn parse(text: &str) -> Result<Parsed, SyntaxError> {
+    let mut reader = Reader { text, pos: 0, duplicates: Vec::new(), spans: Spans::default() };
+    if text.starts_with('\u{feff}') { reader.pos = '\u{feff}'.len_utf8(); }
+    reader.non_execute_prefix()?;
+    if reader.peek_non_whitespace()?.is_none() {
+        return Ok(Parsed { value: Value::Null, duplicates: Vec::new(), spans: reader.spans });
+    }
+    let value = reader.value("")?;
+    if !value.is_null() && reader.peek_non_whitespace()?.is_some() {
+        return Err(reader.error("trailing characters"));
+    }
+    Ok(Parsed { value, duplicates: reader.duplicates, spans: reader.spans })
+}
+
+/// Deserialise an instance of `T` from JSON text read as the game does, reporting the location
+/// of errors as [`error::from_str`] does. Duplicate keys are not reported, use [`parse`] and
+/// [`error::from_value`] for that.
+pub fn from_str<T: DeserializeOwned>(text: &str) -> Result<T, ParseError> {
+    span::from_str(text).map(|(value, _)| value)
+}
+
+struct Reader<'a> {
+    text: &'a str,
+    pos: usize,
+    duplicates: Vec<DuplicateKey>,
+    spans: Spans,
+}
+
+/// Can `c` appear in an unquoted string?
+fn is_literal(c: u8) -> bool {
+    !matches!(c, b'/' | b'\\' | b';' | b'#' | b'=' | b'{' | b'}' | b'[' | b']' | b':' | b','
+        | b' ' | b'\t' | b'\x0C' | b'\r' | b'\n')
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&self) -> &'a [u8] { self.text.as_bytes() }
+
+    fn error_at(&self, pos: usize, message: &str) -> SyntaxError {
+        let (line, column) = span::position(self.text, pos);
+        SyntaxError { message: message.to_string(), line, column }
+    }
+
+    fn error(&self, message: &str) -> SyntaxError { self.error_at(self.pos, message) }
+
+    /// Skip whitespace and comments, and peek at the next character.
+    fn peek_non_whitespace(&mut self) -> Result<Option<u8>, SyntaxError> {
+        let bytes = self.bytes();
+        while let Some(&c) = bytes.get(self.pos) {
+            match c {
+                b' ' | b'\n' | b'\t' | b'\r' => self.pos += 1,
+                b'/' if bytes.get(self.pos + 1) == Some(&b'*') => {
+                    match self.text[self.pos + 2..].find("*/") {
+                        Some(k) => self.pos += k + 4,
+                        None => return Err(self.error("unterminated comment")),
+                    }
+                }
+                b'/' if bytes.get(self.pos + 1) == Some(&b'/') => self.skip_line(),
+                b'#' => self.skip_line(),
+                _ => return Ok(Some(c)),
+            }
+        }
+        Ok(None)
+    }
+
+    fn skip_line(&mut self) {
+        let rest = &self.bytes()[self.pos..];
+        self.pos += rest.iter().position(|&c| c == b'\n' || c == b'\r').map_or(rest.len(), |k| k + 1);
+    }
+
+    /// Skip whitespace and comments, and consume the next character.
+    fn next_non_whitespace(&mut self) -> Result<u8, SyntaxError> {
+        match self.peek_non_whitespace()? {
+            Some(c) => {
+                self.pos += 1;
+                Ok(c)
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn non_execute_prefix(&mut self) -> Result<(), SyntaxError> {
+        self.peek_non_whitespace()?;
+        if self.text[self.pos..].starts_with(")]}'\n") { self.pos += 5; }
+        Ok(())
+    }
+
+    fn value(&mut self, loc: &str) -> Result<Value, SyntaxError> {
+        self.peek_non_whitespace()?;
+        let start = self.pos;
+        let value = match self.next_non_whitespace()? {
+            b'{' => self.object(loc),
+            b'[' => self.array(loc),
+            quote @ (b'"' | b'\'') => self.string(quote).map(Value::String),
+            _ => {
+                self.pos -= 1;
+                self.literal()
+            }
+        }?;
+        self.spans.insert_value(loc.to_string(), Span { start, end: self.pos });
+        Ok(value)
+    }
+
+    fn object(&mut self, loc: &str) -> Result<Value, SyntaxError> {
+        let mut map = Map::new();
+        loop {
+            if !map.is_empty() {
+                match self.next_non_whitespace()? {
+                    b'}' => return Ok(Value::Object(map)),
+                    b',' | b';' => {}
+                    _ => return Err(self.error_at(self.pos - 1, "unterminated object")),
+                }
+            }
+            self.peek_non_whitespace()?;
+            let start = self.pos;
+            let key = match self.next_non_whitespace()? {
+                quote @ (b'"' | b'\'') => self.string(quote)?,
+                b'}' if map.is_empty() => return Ok(Value::Object(map)),
+                c if is_literal(c) => {
+                    self.pos -= 1;
+                    self.unquoted().to_string()
+                }
+                _ => return Err(self.error_at(self.pos - 1, "expected a name")),
+            };
+            let key_end = self.pos;
+            match self.next_non_whitespace()? {
+                b':' => {}
+                b'=' => if self.bytes().get(self.pos) == Some(&b'>') { self.pos += 1 },
+                _ => return Err(self.error_at(self.pos - 1, "expected ':'")),
+            }
+            let path = join(loc, &key);
+            self.spans.insert_key(path.clone(), Span { start, end: key_end });
+            let value = self.value(&path)?;
+            if map.contains_key(&key) {
+                let (line, column) = span::position(self.text, start);
+                self.duplicates.push(DuplicateKey { path, key: key.clone(), line, column });
+            }
+            map.insert(key, value);
+        }
+    }
+
+    fn array(&mut self, loc: &str) -> Result<Value, SyntaxError> {
+        let mut values = Vec::new();
+        loop {
+            if !values.is_empty() {
+                match self.next_non_whitespace()? {
+                    b']' => return Ok(Value::Array(values)),
+                    b',' | b';' => {}
+                    _ => return Err(self.error_at(self.pos - 1, "unterminated array")),
+                }
+            }
+            match self.peek_non_whitespace()? {
+                Some(b']') if values.is_empty() => {
+                    self.pos += 1;
+                    return Ok(Value::Array(values));
+                }
+                // an empty element means null
+                Some(b']' | b',' | b';') => {
+                    let span = Span { start: self.pos, end: self.pos };
+                    self.spans.insert_value(format!("{}[{}]", loc, values.len()), span);
+                    values.push(Value::Null);
+                }
+                _ => {
+                    let loc = format!("{}[{}]", loc, values.len());
+                    values.push(self.value(&loc)?);
+                }
+            }
+        }
+    }
+
+    fn string(&mut self, quote: u8) -> Result<String, SyntaxError> {
+        let start = self.pos - 1;
+        let mut result = String::new();
+        let mut high_surrogate: Option<u32> = None;
+        loop {
+            let rest = &self.text[self.pos..];
+            let k = match rest.bytes().position(|c| c == quote || c == b'\\') {
+                Some(k) => k,
+                None => return Err(self.error_at(start, "unterminated string")),
+            };
+            if k > 0 {
+                result.extend(high_surrogate.take().map(|_| char::REPLACEMENT_CHARACTER));
+                result.push_str(&rest[..k]);
+            }
+            self.pos += k + 1;
+            if rest.as_bytes()[k] == quote {
+                result.extend(high_surrogate.take().map(|_| char::REPLACEMENT_CHARACTER));
+                return Ok(result);
+            }
+            let escape = match self.bytes().get(self.pos) {
+                Some(&c) => c,
+                None => return Err(self.error_at(start, "unterminated string")),
+            };
+            self.pos += 1;
+            let c = match escape {
+                b'u' => {
+                    let code = self.text.get(self.pos..self.pos + 4)
+                        .filter(|hex| hex.bytes().all(|c| c.is_ascii_hexdigit()))
+                        .map(|hex| u32::from_str_radix(hex, 16).unwrap());
+                    let code = match code {
+                        Some(code) => code,
+                        None => return Err(self.error("malformed unicode escape")),
+                    };
+                    self.pos += 4;
+                    match (high_surrogate.take(), code) {
+                        (Some(high), 0xDC00..=0xDFFF) => {
+                            result.push(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00)).unwrap());
+                            continue;
+                        }
+                        (high, 0xD800..=0xDBFF) => {
+                            result.extend(high.map(|_| char::REPLACEMENT_CHARACTER));
+                            high_surrogate = Some(code);
+                            continue;
+                        }
+                        (high, _) => {
+                            result.extend(high.map(|_| char::REPLACEMENT_CHARACTER));
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                    }
+                }
+                b't' => '\t',
+                b'b' => '\x08',
+                b'n' | b'\n' => '\n',
+                b'r' => '\r',
+                b'f' => '\x0C',
+                b'\'' | b'"' | b'\\' | b'/' => escape as char,
+                _ => return Err(self.error_at(self.pos - 2, "invalid escape sequence")),
+            };
+            result.extend(high_surrogate.take().map(|_| char::REPLACEMENT_CHARACTER));
+            result.push(c);
+        }
+    }
+
+    /// A keyword, a number, or an unquoted string.
+    fn literal(&mut self) -> Result<Value, SyntaxError> {
+        let literal = self.unquoted();
+        if literal.is_empty() {
+            return Err(self.error("expected a value"));
+        }
+        Ok(match literal.to_ascii_lowercase().as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" => Value::Null,
+            _ if is_number(literal) => Value::Number(literal.parse::<Number>().unwrap()),
+            _ => Value::String(literal.to_string()),
+        })
+    }
+
+    fn unquoted(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        let len = rest.bytes().position(|c| !is_literal(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+/// Is `literal` a number for Gson? This is the JSON number syntax.
+fn is_number(literal: &str) -> bool {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    let s = literal.strip_prefix('-').unwrap_or(literal);
+    let int = digits(s);
+    if int == 0 || (int > 1 && s.starts_with('0')) { return false; }
+    let mut s = &s[int..];
+    if let Some(rest) = s.strip_prefix('.') {
+        let frac = digits(rest);
+        if frac == 0 { return false; }
+        s = &rest[frac..];
+    }
+    if let Some(rest) = s.strip_prefix(|c| c == 'e' || c == 'E') {
+        let rest = rest.strip_prefix(|c| c == '+' || c == '-').unwrap_or(rest);
+        let exp = digits(rest);
+        if exp == 0 { return false; }
+        s = &rest[exp..];
+    }
+    s.is_empty()
+}
@@ -0,0 +1,158 @@
+/*
+ * minecraft-json: processing Minecraft JSON data
+ * Copyright (C) 2021  Xie Ruifeng
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Source spans of JSON values, for attaching diagnostics to the original text.
+//!
+//! [`from_str`] returns a typed model together with the [`Spans`] of every value in the text,
+//! keyed by the same paths used elsewhere in this crate, e.g. in [`ParseError`] and
+//! [`DanglingReference`]. Any node of the model can thus be mapped back to its source, as long
+//! as its path is known.
+//!
+//! ```
+//! # use minecraft_json::minecraft::data::advancement::Advancement;
+//! # use minecraft_json::minecraft::span;
+//! let json = r#"{
+//!   "display": {
+//!     "icon": {"item": "minecraft:red_bed"},
+//!     "title": {"text": "Sleep", "color": "gold"},
+//!     "description": "Sleep in a bed"
+//!   },
+//!   "criteria": {
+//!     "slept": {"trigger": "minecraft:slept_in_bed"}
+//!   }
+//! }"#;
+//! let (advancement, spans) = span::from_str::<Advancement>(json).unwrap();
+//! let colour = spans.value("display.title.color").unwrap();
+//! assert_eq!(&json[colour.range()], r#""gold""#);
+//! assert_eq!(span::position(json, colour.start), (4, 41));
+//!
+//! let criterion = spans.value("criteria.slept").unwrap();
+//! assert_eq!(&json[criterion.range()], r#"{"trigger": "minecraft:slept_in_bed"}"#);
+//! let key = spans.key("criteria.slept").unwrap();
+//! assert_eq!(&json[key.range()], r#""slept""#);
+//! assert_eq!(spans.at(key.start + 1), Some(("criteria.slept", key)));
+//! // a path inside the criterion, e.g. from a validator, not present in the text
+//! assert_eq!(spans.find("criteria.slept.conditions.player[0]"), Some(("criteria.slept", criterion)));
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use serde::de::DeserializeOwned;
+use crate::minecraft::error::{self, ParseError};
+use crate::minecraft::lenient;
+
+#[cfg(doc)]
+use crate::minecraft::data::validate::DanglingReference;
+
+/// A byte range in the source text.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: usize,
+    /// Offset past the last byte.
+    pub end: usize,
+}
+
+impl Span {
+    /// The span as a range, for slicing the source text.
+    pub fn range(self) -> Range<usize> { self.start..self.end }
+}
+
+/// Spans of values and object keys, keyed by their paths: a dot-separated path of JSON keys,
+/// with array indices in brackets. The path of the top-level value is empty.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct Spans {
+    values: BTreeMap<String, Span>,
+    keys: BTreeMap<String, Span>,
+}
+
+impl Spans {
+    /// Span of the value at `path`, if any.
+    pub fn value(&self, path: &str) -> Option<Span> { self.values.get(path).copied() }
+
+    /// Span of the key of the object member at `path`, quotes included, if any.
+    pub fn key(&self, path: &str) -> Option<Span> { self.keys.get(path).copied() }
+
+    /// Span of the value at `path`, or else that of the innermost enclosing value present in the
+    /// text, together with its path.
+    pub fn find<'a>(&self, mut path: &'a str) -> Option<(&'a str, Span)> {
+        loop {
+            if let Some(span) = self.value(path) {
+                return Some((path, span));
+            }
+            path = &path[..path.rfind(['.', '['])?];
+        }
+    }
+
+    /// The innermost value or key containing the byte at `offset`, with its path, e.g. for
+    /// looking up the node under the cursor in an editor.
+    pub fn at(&self, offset: usize) -> Option<(&str, Span)> {
+        self.values.iter().chain(self.keys.iter())
+            .filter(|(_, span)| span.start <= offset && offset < span.end)
+            .min_by_key(|(_, span)| span.end - span.start)
+            .map(|(path, span)| (path.as_str(), *span))
+    }
+
+    /// All values with their paths, in the order of the paths.
+    pub fn values(&self) -> impl Iterator<Item=(&str, Span)> {
+        self.values.iter().map(|(path, span)| (path.as_str(), *span))
+    }
+
+    pub(crate) fn insert_value(&mut self, path: String, span: Span) { self.values.insert(path, span); }
+
+    pub(crate) fn insert_key(&mut self, path: String, span: Span) { self.keys.insert(path, span); }
+}
+
+/// Append `rest` (an object key, or a path starting with one, or with an array index in brackets)
+/// to the path `path`, in the format used by [`Spans`].
+pub(crate) fn join(path: &str, rest: &str) -> String {
+    if path.is_empty() || rest.is_empty() || rest.starts_with('[') {
+        format!("{}{}", path, rest)
+    } else {
+        format!("{}.{}", path, rest)
+    }
+}
+
+/// Line and column (both starting from 1, the column counted in bytes) of a byte offset.
+pub fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text.as_bytes()[..offset];
+    let line_start = before.iter().rposition(|&c| c == b'\n').map_or(0, |k| k + 1);
+    (before.iter().filter(|&&c| c == b'\n').count() + 1, offset - line_start + 1)
+}
+
+/// Deserialise an instance of `T` from JSON text read as the game does (see [`lenient`]),
+/// together with the spans of all values. Errors are located using the spans.
+pub fn from_str<T: DeserializeOwned>(text: &str) -> Result<(T, Spans), ParseError> {
+    from_parsed(text, lenient::parse(text)?)
+}
+
+/// Same as [`from_str`], for `text` already read by [`lenient::parse`], e.g. to also check the
+/// JSON value or the duplicate keys without reading the text again.
+pub fn from_parsed<T: DeserializeOwned>(text: &str, parsed: lenient::Parsed) -> Result<(T, Spans), ParseError> {
+    match error::from_value(parsed.value) {
+        Ok(value) => Ok((value, parsed.spans)),
+        Err(mut err) => {
+            if let Some((_, span)) = parsed.spans.find(&err.path) {
+                let (line, column) = position(text, span.start);
+                err.line = line;
+                err.column = column;
+            }
+            Err(err)
+        }
+    }
+}
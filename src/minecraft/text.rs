@@ -19,9 +19,14 @@
 //! [Raw JSON text format](https://minecraft.fandom.com/wiki/Raw_JSON_text_format).
 
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
-use serde_json::Number;
+use serde::de::{IntoDeserializer, value::StringDeserializer};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde_json::{Map, Number, Value};
 use std::fmt::{Display, Formatter};
 use crate::defaults;
+use crate::minecraft::common::{Either, Extra};
+use crate::minecraft::error;
 
 /// Text colours, either pre-defined or custom hexadecimal colours.
 ///
@@ -31,7 +36,8 @@ use crate::defaults;
 /// assert_equiv!(r##""#66CCFF""##, Colour::Hex(HexColour { red: 0x66, green: 0xCC, blue: 0xFF }));
 /// ```
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Deserialize, Serialize)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 #[allow(missing_docs)]
 pub enum Colour {
@@ -39,6 +45,16 @@ pub enum Colour {
     Hex(HexColour),
 }
 
+impl<'de> Deserialize<'de> for Colour {
+    /// As in the game, strings starting with `#` are hexadecimal colours, other strings are names.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let hex = text.starts_with('#');
+        let text: StringDeserializer<D::Error> = text.into_deserializer();
+        if hex { HexColour::deserialize(text).map(Colour::Hex) } else { ColourName::deserialize(text).map(Colour::Named) }
+    }
+}
+
 /// Pre-defined colour names.
 ///
 /// ```
@@ -48,6 +64,7 @@ pub enum Colour {
 /// ```
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 #[allow(missing_docs)]
 pub enum ColourName {
@@ -96,6 +113,14 @@ impl Display for HexColour {
     }
 }
 
+#[cfg(feature = "schema")]
+impl JsonSchema for HexColour {
+    fn schema_name() -> String { "HexColour".to_string() }
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::minecraft::common::string_schema("^#[0-9a-fA-F]{6}$", "Hexadecimal colour, e.g. `#FF0000`.")
+    }
+}
+
 impl Serialize for HexColour {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         format!("{}", self).serialize(s)
@@ -117,7 +142,8 @@ impl<'de> Deserialize<'de> for HexColour {
                 + (s[k + 1] as char).to_digit(16).unwrap() as u8
         }
 
-        let text = <&str>::deserialize(d)?;
+        let text = String::deserialize(d)?;
+        let text = text.as_str();
         if !check_format(text) {
             return Err(de::Error::invalid_value(
                 Unexpected::Str(text),
@@ -139,6 +165,7 @@ impl<'de> Deserialize<'de> for HexColour {
 /// virtually all properties are optional and may be left out.
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct TextComponentTags {
     /// A list of additional raw JSON text components to be displayed after this one.
     ///
@@ -147,16 +174,34 @@ pub struct TextComponentTags {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra: Vec<TextComponent>,
     /// Formatting properties.
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "error::flattened")]
     pub formatting: Formatting,
     /// Interactivity properties.
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "error::flattened")]
     pub interactivity: Interactivity,
+    /// Fields not known to this crate, see [`Extra`].
+    ///
+    /// ```
+    /// # use minecraft_json::minecraft::{error, text::TextComponent};
+    /// let json = r#"{"text":"x","colour":"red"}"#;
+    /// let component: TextComponent = serde_json::from_str(json).unwrap();
+    /// match &component {
+    ///     TextComponent::Text { properties, .. } => assert_eq!(properties.unknown.0["colour"], "red"),
+    ///     _ => unreachable!(),
+    /// }
+    /// assert_eq!(serde_json::to_string(&component).unwrap(), json);
+    ///
+    /// let err = error::strict(|| error::from_str::<TextComponent>(json)).unwrap_err();
+    /// assert_eq!(err.to_string(), "colour: unknown field at line 1 column 22");
+    /// ```
+    #[serde(flatten)]
+    pub unknown: Extra,
 }
 
 /// Formatting properties for a [`TextComponent`], can be inherited.
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Formatting {
     /// The color to render the content in. See also [`Colour`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -186,6 +231,7 @@ pub struct Formatting {
 /// Interactivity properties for a [`TextComponent`], can be inherited.
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(default, rename_all = "camelCase")]
 pub struct Interactivity {
     /// When the text is shift-clicked by a player, this string is inserted in their chat input.
@@ -194,29 +240,47 @@ pub struct Interactivity {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub insertion: Option<String>,
     /// Allows for events to occur when the player clicks on text. Only work in chat messages
-    /// and written books, unless specified otherwise.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// and written books, unless specified otherwise. Also accepted as `click_event`, the
+    /// spelling since 1.21.5.
+    #[serde(default, alias = "click_event", skip_serializing_if = "Option::is_none")]
     pub click_event: Option<Box<ClickEvent>>,
-    /// Allows for a tooltip to be displayed when the player hovers their mouse over text.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Allows for a tooltip to be displayed when the player hovers their mouse over text. Also
+    /// accepted as `hover_event`, the spelling since 1.21.5.
+    #[serde(default, alias = "hover_event", skip_serializing_if = "Option::is_none")]
     pub hover_event: Option<Box<HoverEvent>>,
 }
 
 /// Event upon mouse click on this text component.
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct ClickEvent {
     /// The action to perform when clicked. See also [`ClickEventAction`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
     action: Option<ClickEventAction>,
     /// The URL, file path, chat, command or book page used by the specified action.
+    /// Since 1.21.5, only used by [`ClickEventAction::CopyToClipboard`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
     value: Option<String>,
+    /// The URL for [`ClickEventAction::OpenUrl`], since 1.21.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    /// The file path for [`ClickEventAction::OpenFile`], since 1.21.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// The command for [`ClickEventAction::RunCommand`] and [`ClickEventAction::SuggestCommand`],
+    /// since 1.21.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    command: Option<String>,
+    /// The book page for [`ClickEventAction::ChangePage`], since 1.21.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page: Option<Number>,
 }
 
 /// Action performed upon a [`ClickEvent`].
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ClickEventAction {
     /// Opens `value` as a URL in the user's default web browser.
@@ -247,6 +311,7 @@ pub enum ClickEventAction {
 /// Part of [`HoverEvent`].
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ValueOrContents<V, C> {
     /// The deprecated `value` field.
@@ -259,7 +324,8 @@ pub enum ValueOrContents<V, C> {
 ///
 /// ```
 /// # use minecraft_json::{assert_equiv, minecraft::text};
-/// # use text::{HoverEvent, ValueOrContents, TextComponent, TextComponentTags, StringLike};
+/// # use minecraft_json::minecraft::common::Either;
+/// # use text::{HoverEvent, ValueOrContents, TextComponent, TextComponentTags, StringLike, Item};
 /// assert_equiv!(
 ///     r#"{"action":"show_text","contents":{"text":"hello"}}"#,
 ///     HoverEvent::ShowText(ValueOrContents::Contents(
@@ -269,24 +335,32 @@ pub enum ValueOrContents<V, C> {
 ///         })
 ///     ))
 /// );
+/// assert_equiv!(
+///     r#"{"action":"show_item","id":"minecraft:stick"}"#,
+///     HoverEvent::ShowItem(Either::Right(Item { id: "minecraft:stick".into(), ..Item::default() }))
+/// );
 /// ```
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(tag = "action", rename_all = "snake_case")]
 pub enum HoverEvent {
     /// Shows a raw JSON text component. `value`/`contents` can be any valid text component type.
     /// Note that `clickEvent` and `hoverEvent` do not function within the tooltip.
     ShowText(ValueOrContents<Box<TextComponent>, Box<TextComponent>>),
     /// Shows the tooltip of an item as if it was being hovering over it in an inventory.
-    ShowItem(ValueOrContents<String, Item>),
+    /// Since 1.21.5, the [`Item`] is written inline.
+    ShowItem(Either<ValueOrContents<String, Item>, Item>),
     /// Shows an entity's name, type, and UUID.
     /// Used by `selector` (see [`TextComponent::EntityNames`]).
-    ShowEntity(ValueOrContents<LegacyEntity, Entity>),
+    /// Since 1.21.5, the entity is written inline as an [`InlineEntity`].
+    ShowEntity(Either<ValueOrContents<LegacyEntity, Entity>, InlineEntity>),
 }
 
 /// The item that should be displayed by [`HoverEvent::ShowItem`].
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Item {
     /// The namespaced item ID. Present minecraft:air if invalid.
     pub id: String,
@@ -296,11 +370,15 @@ pub struct Item {
     /// A string containing the serialized NBT of the additional information about the item.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>,
+    /// Item components, replacing `tag` since 1.20.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Map<String, Value>>,
 }
 
 /// The entity that should be displayed by [`HoverEvent::ShowEntity`].
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Entity {
     /// Hidden if not present. A raw JSON text that is displayed as the name of the entity.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -313,9 +391,24 @@ pub struct Entity {
     pub id: String,
 }
 
+/// The entity that should be displayed by [`HoverEvent::ShowEntity`], since 1.21.5.
+#[derive(Eq, PartialEq, Default, Debug)]
+#[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct InlineEntity {
+    /// Hidden if not present. A raw JSON text that is displayed as the name of the entity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<Box<TextComponent>>,
+    /// The namespaced entity ID.
+    pub id: String,
+    /// The UUID of the entity, either in the hyphenated hexadecimal format or as four integers.
+    pub uuid: Either<String, Vec<i32>>,
+}
+
 /// The entity that should be displayed by [`HoverEvent::ShowEntity`].
 #[derive(Eq, PartialEq, Default, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct LegacyEntity {
     /// Hidden if not present. An NBT string containing some JSON that is parsed as a text
     /// component and displayed as the name of the entity. If the NBT string cannot be parsed
@@ -336,6 +429,7 @@ pub struct LegacyEntity {
 /// This component is resolved into a text component containing the scoreboard value.
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Score {
     /// The name of the score holder whose score should be displayed.
     /// This can be a selector like `@p` or an explicit name. If the text is a selector, the
@@ -363,7 +457,8 @@ pub struct Score {
 /// assert_equiv!("true", StringLike::Boolean(true));
 /// ```
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Deserialize, Serialize)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 pub enum StringLike {
     /// A boolean is converted to a string ("true" or "false") to display directly.
@@ -378,11 +473,33 @@ pub enum StringLike {
     String(String),
 }
 
+impl<'de> Deserialize<'de> for StringLike {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        error::untagged(deserializer, "StringLike", &[
+            &|v| error::variant(v, StringLike::Boolean),
+            &|v| error::variant(v, StringLike::Number),
+            &|v| error::variant(v, StringLike::String),
+        ])
+    }
+}
+
 /// Raw JSON text is made up of text components. There is a single root component, which can
 /// have child components, which can have their own children and so on. Components can also
 /// have formatting and interactivity added to them, which is inherited by their children.
+///
+/// Errors are reported for the kind of component the object is meant to be:
+///
+/// ```
+/// # use minecraft_json::minecraft::{error, text::TextComponent};
+/// let err = error::from_str::<TextComponent>(r#"{"text": "x", "color": "notacolour"}"#).unwrap_err();
+/// assert_eq!(err.path, "color");
+/// assert!(err.message.starts_with("unknown variant `notacolour`, expected one of `black`"));
+/// let err = error::from_str::<TextComponent>(r#"["x", {"text": "y", "bold": "yes"}]"#).unwrap_err();
+/// assert_eq!(err.to_string(), r#"[1].bold: invalid type: string "yes", expected a boolean at line 1 column 29"#);
+/// ```
 #[derive(Eq, PartialEq, Debug)]
-#[derive(Deserialize, Serialize)]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 pub enum TextComponent {
     /// Displays plain text.
@@ -526,3 +643,68 @@ pub enum TextComponent {
         properties: TextComponentTags,
     },
 }
+
+impl<'de> Deserialize<'de> for TextComponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Text {
+            text: StringLike,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        #[derive(Deserialize)]
+        struct Translated {
+            translate: String,
+            #[serde(default)]
+            with: Vec<TextComponent>,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        #[derive(Deserialize)]
+        struct ScoreBoard {
+            score: Score,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        #[derive(Deserialize)]
+        struct EntityNames {
+            selector: String,
+            #[serde(default = "defaults::entity_names_separator")]
+            separator: Box<TextComponent>,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        #[derive(Deserialize)]
+        struct KeyBind {
+            keybind: String,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        #[derive(Deserialize)]
+        struct NbtValue {
+            nbt: String,
+            #[serde(default)]
+            interpret: bool,
+            separator: Box<TextComponent>,
+            block: Option<String>,
+            entity: Option<String>,
+            storage: Option<String>,
+            #[serde(flatten, deserialize_with = "error::flattened")]
+            properties: TextComponentTags,
+        }
+        error::untagged(deserializer, "TextComponent", &[
+            &|v| error::variant(v, TextComponent::RawTextLike),
+            &|v| error::variant(v, TextComponent::RawTextList),
+            &|v| error::variant(v, |Text { text, properties }| TextComponent::Text { text, properties }),
+            &|v| error::variant(v, |Translated { translate, with, properties }|
+                TextComponent::Translated { translate, with, properties }),
+            &|v| error::variant(v, |ScoreBoard { score, properties }|
+                TextComponent::ScoreBoard { score, properties }),
+            &|v| error::variant(v, |EntityNames { selector, separator, properties }|
+                TextComponent::EntityNames { selector, separator, properties }),
+            &|v| error::variant(v, |KeyBind { keybind, properties }| TextComponent::KeyBind { keybind, properties }),
+            &|v| error::variant(v, |NbtValue { nbt, interpret, separator, block, entity, storage, properties }|
+                TextComponent::NbtValue { nbt, interpret, separator, block, entity, storage, properties }),
+        ])
+    }
+}